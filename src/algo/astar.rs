@@ -0,0 +1,175 @@
+use super::{Measure, ShortestPaths};
+use crate::types::{EdgeRef, Graph};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Finds the cheapest path from `start` to the first node accepted by `is_goal`
+///
+/// `edge_cost` returns cost of moving along edge and `heuristic` returns
+/// estimated cost from node to the goal, which should never overestimate it,
+/// otherwise found path may not be the cheapest one
+///
+/// Returns nodes of path including both ends and it's total cost
+/// or None if no goal is reachable
+///
+/// Computes in **O((n + e) * log(n))** where n = node count, e = edge count
+/// for consistent heuristic
+///
+/// **Panics** if start not exists
+pub fn astar<G, K, IsGoal, EdgeCost, Heuristic>(
+    graph: &G,
+    start: usize,
+    mut is_goal: IsGoal,
+    mut edge_cost: EdgeCost,
+    mut heuristic: Heuristic,
+) -> Option<(Vec<usize>, K)>
+where
+    G: Graph,
+    K: Measure,
+    IsGoal: FnMut(usize) -> bool,
+    EdgeCost: FnMut(EdgeRef<'_, G::Weight>) -> K,
+    Heuristic: FnMut(usize) -> K,
+{
+    if graph.get_node_by_index(start).is_none() {
+        panic!("Node with index {} not found", start);
+    }
+
+    let mut paths = ShortestPaths::new(start, graph.node_bound());
+    let mut heap = BinaryHeap::from([Reverse((heuristic(start), start, K::zero()))]);
+
+    while let Some(Reverse((_, node, cost))) = heap.pop() {
+        if paths.distance(node).is_some_and(|best| best < cost) {
+            continue;
+        }
+
+        if is_goal(node) {
+            return Some((paths.path_to(node)?, cost));
+        }
+
+        for edge in graph.edges_from(node) {
+            let candidate = cost + edge_cost(edge);
+            if paths
+                .distance(edge.to)
+                .is_none_or(|current| candidate < current)
+            {
+                paths.relax(edge.to, node, candidate);
+                heap.push(Reverse((
+                    candidate + heuristic(edge.to),
+                    edge.to,
+                    candidate,
+                )));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix_graph::MatrixGraph;
+    use crate::types::GetNodeByIndex;
+
+    /// Grid 3 x 3 where node is `(row, column)` and every move costs 1
+    fn create_grid() -> MatrixGraph<(u32, u32), u32> {
+        let mut g = MatrixGraph::default();
+        for row in 0..3 {
+            for column in 0..3 {
+                g.add_node((row, column));
+            }
+        }
+
+        for row in 0..3 {
+            for column in 0..3 {
+                let from = g.get_index_of(&(row, column)).unwrap();
+                for (r, c) in [(row + 1, column), (row, column + 1)] {
+                    if let Some(to) = g.get_index_of(&(r, c)) {
+                        g.add_edge(from, to, 1);
+                        g.add_edge(to, from, 1);
+                    }
+                }
+            }
+        }
+        g
+    }
+
+    fn manhattan(g: &MatrixGraph<(u32, u32), u32>, idx: usize, goal: (u32, u32)) -> u32 {
+        let (row, column) = *g.get_node_by_index(idx).unwrap();
+        row.abs_diff(goal.0) + column.abs_diff(goal.1)
+    }
+
+    #[test]
+    fn test_finds_path_on_grid() {
+        let mut g = create_grid();
+        let (center, bottom) = (
+            g.get_index_of(&(1, 1)).unwrap(),
+            g.get_index_of(&(2, 1)).unwrap(),
+        );
+        g.remove_edge(center, bottom);
+
+        let start = g.get_index_of(&(0, 1)).unwrap();
+        let (path, cost) = astar(
+            &g,
+            start,
+            |idx| idx == bottom,
+            |edge| *edge.weight,
+            |idx| manhattan(&g, idx, (2, 1)),
+        )
+        .unwrap();
+
+        assert_eq!(cost, 4);
+        assert_eq!(path.len(), 5);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&bottom));
+        for pair in path.windows(2) {
+            assert!(g.contains_edge(pair[0], pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_uses_edge_cost_closure() {
+        let g = MatrixGraph::<char, u32>::from_edges([
+            ('a', 'b', 1),
+            ('b', 'd', 1),
+            ('a', 'c', 5),
+            ('c', 'd', 5),
+        ]);
+        let d = g.get_index_of(&'d').unwrap();
+
+        let (path, cost) = astar(&g, 0, |idx| idx == d, |edge| *edge.weight, |_| 0).unwrap();
+        assert_eq!(path, vec![0, 1, d]);
+        assert_eq!(cost, 2);
+
+        let c = g.get_index_of(&'c').unwrap();
+        let through_c = |edge: EdgeRef<'_, u32>| {
+            if edge.to == c || edge.from == c {
+                0
+            } else {
+                10
+            }
+        };
+        let (path, cost) = astar(&g, 0, |idx| idx == d, through_c, |_| 0).unwrap();
+        assert_eq!(path, vec![0, c, d]);
+        assert_eq!(cost, 0);
+    }
+
+    #[test]
+    fn test_returns_none_if_goal_unreachable() {
+        let mut g = MatrixGraph::<u8, u32>::from_edges([(1, 2, 1)]);
+        let isolated = g.add_node(3);
+
+        assert_eq!(astar(&g, 0, |idx| idx == isolated, |_| 1, |_| 0u32), None);
+        assert_eq!(
+            astar(&g, 0, |idx| idx == 0, |_| 1, |_| 0u32),
+            Some((vec![0], 0))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Node with index 4 not found")]
+    fn test_panics_on_not_existing_start() {
+        let g = MatrixGraph::<u8, u32>::from_edges([(1, 2, 1)]);
+        astar(&g, 4, |_| true, |_| 1, |_| 0u32);
+    }
+}
@@ -0,0 +1,161 @@
+use super::{Measure, ShortestPaths};
+use crate::error::{Cycle, NegativeCycle};
+use crate::types::Graph;
+
+/// Finds the shortest paths from `source` to all reachable nodes,
+/// edge weights can be negative
+///
+/// Returns [`NegativeCycle`] with nodes of cycle if it is reachable from source
+///
+/// Computes in **O(n * e)** where n = node count, e = edge count
+///
+/// **Panics** if source not exists
+pub fn bellman_ford<G>(graph: &G, source: usize) -> Result<ShortestPaths<G::Weight>, NegativeCycle>
+where
+    G: Graph,
+    G::Weight: Measure,
+{
+    if graph.get_node_by_index(source).is_none() {
+        panic!("Node with index {} not found", source);
+    }
+
+    let mut paths = ShortestPaths::new(source, graph.node_bound());
+
+    for _ in 1..graph.node_count() {
+        if relax_edges(graph, &mut paths).is_none() {
+            return Ok(paths);
+        }
+    }
+
+    match relax_edges(graph, &mut paths) {
+        Some(node) => Err(NegativeCycle(Cycle::new(find_cycle(
+            &paths,
+            node,
+            graph.node_count(),
+        )))),
+        None => Ok(paths),
+    }
+}
+
+/// Relaxes every edge once, returns last node with changed distance
+fn relax_edges<G>(graph: &G, paths: &mut ShortestPaths<G::Weight>) -> Option<usize>
+where
+    G: Graph,
+    G::Weight: Measure,
+{
+    let mut changed = None;
+
+    for from in graph.node_indices() {
+        let Some(distance) = paths.distance(from) else {
+            continue;
+        };
+
+        for edge in graph.edges_from(from) {
+            let candidate = distance + *edge.weight;
+            if paths
+                .distance(edge.to)
+                .is_none_or(|current| candidate < current)
+            {
+                paths.relax(edge.to, from, candidate);
+                changed = Some(edge.to);
+            }
+        }
+    }
+
+    changed
+}
+
+/// Walks predecessors from node changed on n-th iteration
+/// which leads into negative cycle
+fn find_cycle<K: Measure>(paths: &ShortestPaths<K>, node: usize, node_count: usize) -> Vec<usize> {
+    let mut start = node;
+    for _ in 0..node_count {
+        start = paths
+            .predecessor(start)
+            .expect("Relaxed node has predecessor");
+    }
+
+    let mut cycle = vec![start];
+    let mut current = paths
+        .predecessor(start)
+        .expect("Relaxed node has predecessor");
+    while current != start {
+        cycle.push(current);
+        current = paths
+            .predecessor(current)
+            .expect("Relaxed node has predecessor");
+    }
+
+    cycle.reverse();
+    cycle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix_graph::MatrixGraph;
+
+    #[test]
+    fn test_finds_distances_with_negative_weights() {
+        let g = MatrixGraph::<u8, i32>::from_edges([
+            (0, 1, 4),
+            (0, 2, 5),
+            (1, 3, -3),
+            (2, 1, -2),
+            (3, 4, 2),
+        ]);
+        let paths = bellman_ford(&g, 0).unwrap();
+
+        assert_eq!(
+            paths.distances(),
+            &[Some(0), Some(3), Some(5), Some(0), Some(2)]
+        );
+        assert_eq!(paths.path_to(4), Some(vec![0, 2, 1, 3, 4]));
+    }
+
+    #[test]
+    fn test_unreachable_nodes_have_no_distance() {
+        let mut g = MatrixGraph::<u8, i32>::from_edges([(0, 1, -1)]);
+        g.add_node(2);
+        let paths = bellman_ford(&g, 1).unwrap();
+
+        assert_eq!(paths.distances(), &[None, Some(0), None]);
+        assert_eq!(paths.predecessor(0), None);
+    }
+
+    #[test]
+    fn test_returns_reachable_negative_cycle() {
+        let g = MatrixGraph::<u8, i32>::from_edges([
+            (0, 1, 1),
+            (1, 2, 1),
+            (2, 3, -2),
+            (3, 1, -1),
+            (3, 4, 1),
+        ]);
+        let error = bellman_ford(&g, 0).unwrap_err();
+
+        let mut cycle = error.0.nodes().to_vec();
+        let start = cycle.iter().position(|idx| *idx == 1).unwrap();
+        cycle.rotate_left(start);
+        assert_eq!(cycle, vec![1, 2, 3]);
+        assert_eq!(
+            NegativeCycle(Cycle::new(vec![1, 2, 3])).to_string(),
+            "Negative cycle found: 1 2 3 1"
+        );
+    }
+
+    #[test]
+    fn test_ignores_unreachable_negative_cycle() {
+        let g = MatrixGraph::<u8, i32>::from_edges([(0, 1, 2), (2, 3, -1), (3, 2, -1)]);
+        let paths = bellman_ford(&g, 0).unwrap();
+
+        assert_eq!(paths.distances(), &[Some(0), Some(2), None, None]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Node with index 5 not found")]
+    fn test_panics_on_not_existing_source() {
+        let g = MatrixGraph::<u8, i32>::from_edges([(0, 1, 2)]);
+        bellman_ford(&g, 5).unwrap();
+    }
+}
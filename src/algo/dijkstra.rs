@@ -0,0 +1,149 @@
+use super::{Measure, ShortestPaths};
+use crate::types::Graph;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Finds the shortest paths from `source` to all reachable nodes
+/// using edge weights as costs
+///
+/// Weights should be non-negative, otherwise result is unspecified,
+/// use [`bellman_ford`](fn@super::bellman_ford) for negative weights
+///
+/// Computes in **O((n + e) * log(n))** where n = node count, e = edge count
+///
+/// **Panics** if source not exists
+pub fn dijkstra<G>(graph: &G, source: usize) -> ShortestPaths<G::Weight>
+where
+    G: Graph,
+    G::Weight: Measure,
+{
+    if graph.get_node_by_index(source).is_none() {
+        panic!("Node with index {} not found", source);
+    }
+
+    let mut paths = ShortestPaths::new(source, graph.node_bound());
+    let mut visited = vec![false; graph.node_bound()];
+    let mut heap = BinaryHeap::from([Reverse((G::Weight::zero(), source))]);
+
+    while let Some(Reverse((distance, node))) = heap.pop() {
+        if visited[node] {
+            continue;
+        }
+        visited[node] = true;
+
+        for edge in graph.edges_from(node) {
+            let candidate = distance + *edge.weight;
+            let is_shorter = paths
+                .distance(edge.to)
+                .is_none_or(|current| candidate < current);
+
+            if !visited[edge.to] && is_shorter {
+                paths.relax(edge.to, node, candidate);
+                heap.push(Reverse((candidate, edge.to)));
+            }
+        }
+    }
+
+    paths
+}
+
+/// Finds the shortest path between two nodes
+///
+/// Returns nodes of path including both ends and it's total cost
+/// or None if `to` is unreachable
+///
+/// **Panics** if `from` not exists
+pub fn shortest_path<G>(graph: &G, from: usize, to: usize) -> Option<(Vec<usize>, G::Weight)>
+where
+    G: Graph,
+    G::Weight: Measure,
+{
+    let paths = dijkstra(graph, from);
+    Some((paths.path_to(to)?, paths.distance(to)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::list_graph::ListGraph;
+    use crate::matrix_graph::MatrixGraph;
+    use crate::types::GetNodeByIndex;
+
+    fn create_graph() -> MatrixGraph<char, u32> {
+        MatrixGraph::from_edges([
+            ('a', 'b', 7),
+            ('a', 'c', 9),
+            ('a', 'f', 14),
+            ('b', 'c', 10),
+            ('b', 'd', 15),
+            ('c', 'd', 11),
+            ('c', 'f', 2),
+            ('d', 'e', 6),
+            ('f', 'e', 9),
+        ])
+    }
+
+    #[test]
+    fn test_finds_distances_from_source() {
+        let g = create_graph();
+        let paths = dijkstra(&g, 0);
+
+        let expected = [
+            ('a', 0),
+            ('b', 7),
+            ('c', 9),
+            ('d', 20),
+            ('e', 20),
+            ('f', 11),
+        ];
+        for (node, distance) in expected {
+            assert_eq!(
+                paths.distance(g.get_index_of(&node).unwrap()),
+                Some(distance)
+            );
+        }
+        assert_eq!(paths.source(), 0);
+        assert_eq!(paths.predecessor(0), None);
+    }
+
+    #[test]
+    fn test_returns_shortest_path_with_cost() {
+        let g = create_graph();
+        let (a, e) = (g.get_index_of(&'a').unwrap(), g.get_index_of(&'e').unwrap());
+
+        let (path, cost) = shortest_path(&g, a, e).unwrap();
+        let nodes = path
+            .iter()
+            .map(|idx| *g.get_node_by_index(*idx).unwrap())
+            .collect::<String>();
+
+        assert_eq!(nodes, "acfe");
+        assert_eq!(cost, 20);
+        assert_eq!(shortest_path(&g, a, a), Some((vec![a], 0)));
+    }
+
+    #[test]
+    fn test_unreachable_node_has_no_path() {
+        let mut g = create_graph();
+        let isolated = g.add_node('z');
+        let e = g.get_index_of(&'e').unwrap();
+
+        let paths = dijkstra(&g, e);
+        assert_eq!(paths.distance(0), None);
+        assert_eq!(paths.path_to(isolated), None);
+        assert_eq!(shortest_path(&g, 0, isolated), None);
+    }
+
+    #[test]
+    fn test_runs_on_list_graph() {
+        let g = ListGraph::<u8, u64>::from_edges([(1, 2, 4), (1, 3, 1), (3, 2, 1)]);
+        assert_eq!(shortest_path(&g, 0, 1), Some((vec![0, 2, 1], 2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Node with index 3 not found")]
+    fn test_panics_on_not_existing_source() {
+        let g = MatrixGraph::<u8, u32>::from_edges([(1, 2, 1)]);
+        dijkstra(&g, 3);
+    }
+}
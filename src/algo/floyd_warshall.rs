@@ -0,0 +1,354 @@
+use super::Measure;
+use crate::error::{Cycle, NegativeCycle};
+use crate::matrix_graph::MatrixGraph;
+use crate::node_storage::{DefaultBuildHasher, NodeStorage};
+use crate::types::{Adjacency, AdjacencyMatrix, GetEdgeByIndex, Graph, MatrixGraphNode};
+use std::hash::BuildHasher;
+
+/// Shortest paths between every pair of nodes
+///
+/// Distances and next hops are stored in row-major matrices
+/// with the same layout as adjacency matrix of source graph
+#[derive(Debug)]
+pub struct AllPairsShortestPaths<'a, N, K, S = DefaultBuildHasher>
+where
+    N: MatrixGraphNode,
+{
+    nodes: &'a NodeStorage<N, S>,
+    distances: Vec<Option<K>>,
+    next: Vec<Option<usize>>,
+    stride: usize,
+}
+
+impl<'a, N, K, S> AllPairsShortestPaths<'a, N, K, S>
+where
+    N: MatrixGraphNode,
+    K: Measure,
+    S: BuildHasher,
+{
+    /// Returns cost of the shortest path between nodes
+    /// or None if `to` is unreachable from `from`
+    #[inline]
+    pub fn distance(&self, from: usize, to: usize) -> Option<K> {
+        *self.distances.get(self.position(from, to)?)?
+    }
+
+    /// Returns node following `from` on the shortest path to `to`
+    /// or None if `to` is unreachable from `from`
+    #[inline]
+    pub fn next_hop(&self, from: usize, to: usize) -> Option<usize> {
+        *self.next.get(self.position(from, to)?)?
+    }
+
+    /// Returns nodes of the shortest path from `from` to `to`
+    /// including both ends or None if `to` is unreachable
+    ///
+    /// Computes in **O(l)** where l = path length
+    pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let mut path = vec![from];
+        let mut current = from;
+
+        while current != to {
+            current = self.next_hop(current, to)?;
+            path.push(current);
+        }
+
+        self.distance(from, to).map(|_| path)
+    }
+
+    /// Returns distances as adjacency matrix,
+    /// where edge weight is cost of the shortest path
+    #[inline]
+    pub fn distance_matrix(&self) -> AdjacencyMatrix<'_, N, K, S> {
+        AdjacencyMatrix {
+            nodes: self.nodes,
+            edges: &self.distances,
+            stride: self.stride,
+        }
+    }
+
+    #[inline]
+    fn position(&self, from: usize, to: usize) -> Option<usize> {
+        (from < self.stride && to < self.stride).then_some(from * self.stride + to)
+    }
+}
+
+/// Finds the shortest paths between every pair of nodes,
+/// edge weights can be negative
+///
+/// Returns [`NegativeCycle`] with nodes of cycle if graph contains one
+///
+/// Computes in **O(n ^ 3)** where n = node count
+pub fn floyd_warshall<N, K, S>(
+    graph: &MatrixGraph<N, K, S>,
+) -> Result<AllPairsShortestPaths<'_, N, K, S>, NegativeCycle>
+where
+    N: MatrixGraphNode + Clone,
+    K: Measure,
+    S: BuildHasher,
+{
+    let adjacency = graph.get_adjacency_matrix();
+    let stride = adjacency.stride;
+    let indices = graph.node_indices().collect::<Vec<_>>();
+
+    let mut distances = adjacency.edges.to_vec();
+    let mut next = adjacency
+        .edges
+        .iter()
+        .enumerate()
+        .map(|(position, edge)| edge.map(|_| position % stride))
+        .collect::<Vec<_>>();
+
+    for &idx in &indices {
+        let position = idx * stride + idx;
+        match distances[position] {
+            Some(distance) if distance < K::zero() => {
+                return Err(NegativeCycle(Cycle::new(vec![idx])));
+            }
+            Some(distance) if distance == K::zero() => {}
+            _ => {
+                distances[position] = Some(K::zero());
+                next[position] = Some(idx);
+            }
+        }
+    }
+
+    for &k in &indices {
+        for &i in &indices {
+            let Some(to_k) = distances[i * stride + k] else {
+                continue;
+            };
+
+            // Row and column of k are not changed on this pass,
+            // so cycle is found before any distance could overflow
+            if let Some(from_k) = distances[k * stride + i] {
+                if to_k + from_k < K::zero() {
+                    let mut walk = hops(&next, stride, i, k);
+                    walk.extend(hops(&next, stride, k, i));
+                    return Err(NegativeCycle(Cycle::new(simple_negative_cycle(
+                        graph, walk,
+                    ))));
+                }
+            }
+
+            for &j in &indices {
+                let Some(from_k) = distances[k * stride + j] else {
+                    continue;
+                };
+
+                let candidate = to_k + from_k;
+                let position = i * stride + j;
+                if distances[position].is_none_or(|current| candidate < current) {
+                    distances[position] = Some(candidate);
+                    next[position] = next[i * stride + k];
+                }
+            }
+        }
+    }
+
+    Ok(AllPairsShortestPaths {
+        nodes: adjacency.nodes,
+        distances,
+        next,
+        stride,
+    })
+}
+
+/// Returns nodes of the shortest path from `from` to `to` excluding `to`
+fn hops(next: &[Option<usize>], stride: usize, from: usize, to: usize) -> Vec<usize> {
+    let mut hops = Vec::new();
+    let mut current = from;
+
+    while current != to {
+        hops.push(current);
+        current = next[current * stride + to].expect("Path to reachable node has next hop");
+    }
+
+    hops
+}
+
+/// Splits closed walk with negative weight by repeated nodes
+/// until it becomes simple cycle, keeping negative part every time
+fn simple_negative_cycle<N, K, S>(graph: &MatrixGraph<N, K, S>, mut walk: Vec<usize>) -> Vec<usize>
+where
+    N: MatrixGraphNode,
+    K: Measure,
+    S: BuildHasher,
+{
+    let weight = |cycle: &[usize]| {
+        cycle
+            .iter()
+            .zip(cycle.iter().cycle().skip(1))
+            .map(|(from, to)| {
+                *graph
+                    .get_edge_by_index(*from, *to)
+                    .expect("Walk follows edges")
+            })
+            .fold(K::zero(), |total, weight| total + weight)
+    };
+
+    let mut first_seen = vec![None; graph.node_bound()];
+    let mut position = 0;
+    while position < walk.len() {
+        let Some(start) = first_seen[walk[position]] else {
+            first_seen[walk[position]] = Some(position);
+            position += 1;
+            continue;
+        };
+
+        let inner = walk[start..position].to_vec();
+        if weight(&inner) < K::zero() {
+            walk = inner;
+        } else {
+            walk.drain(start..position);
+        }
+
+        first_seen.iter_mut().for_each(|seen| *seen = None);
+        position = 0;
+    }
+
+    walk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::GetNodeByIndex;
+
+    fn create_graph() -> MatrixGraph<u8, i32> {
+        MatrixGraph::from_edges([(1, 3, -2), (2, 1, 4), (2, 3, 3), (3, 4, 2), (4, 2, -1)])
+    }
+
+    #[test]
+    fn test_finds_distances_between_all_pairs() {
+        let g = create_graph();
+        let paths = floyd_warshall(&g).unwrap();
+
+        let expected = [[0, -1, -2, 0], [4, 0, 2, 4], [5, 1, 0, 2], [3, -1, 1, 0]];
+        for (from, row) in (1..=4).zip(expected) {
+            for (to, distance) in (1..=4).zip(row) {
+                let (from, to) = (g.get_index_of(&from).unwrap(), g.get_index_of(&to).unwrap());
+                assert_eq!(paths.distance(from, to), Some(distance));
+            }
+        }
+    }
+
+    #[test]
+    fn test_reconstructs_paths() {
+        let g = create_graph();
+        let paths = floyd_warshall(&g).unwrap();
+        let nodes = |path: Vec<usize>| {
+            path.iter()
+                .map(|idx| *g.get_node_by_index(*idx).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let (first, second) = (g.get_index_of(&1).unwrap(), g.get_index_of(&2).unwrap());
+        assert_eq!(paths.path(first, second).map(nodes), Some(vec![1, 3, 4, 2]));
+        assert_eq!(paths.next_hop(first, second), g.get_index_of(&3));
+        assert_eq!(paths.path(second, first), Some(vec![second, first]));
+        assert_eq!(paths.path(first, first), Some(vec![first]));
+        assert_eq!(paths.path(first, 7), None);
+    }
+
+    #[test]
+    fn test_unreachable_pairs_have_no_path() {
+        let mut g = MatrixGraph::<u8, u32>::from_edges([(1, 2, 5)]);
+        g.add_node(3);
+        let paths = floyd_warshall(&g).unwrap();
+
+        assert_eq!(paths.distance(0, 1), Some(5));
+        assert_eq!(paths.distance(1, 0), None);
+        assert_eq!(paths.path(0, 2), None);
+        assert_eq!(paths.next_hop(2, 0), None);
+    }
+
+    #[test]
+    fn test_skips_removed_nodes() {
+        let mut g = MatrixGraph::<u8, u32>::from_edges([(1, 2, 1), (2, 3, 1), (1, 3, 5)]);
+        g.remove_node(1);
+        let paths = floyd_warshall(&g).unwrap();
+
+        assert_eq!(paths.distance(0, 2), Some(5));
+        assert_eq!(paths.distance(1, 1), None);
+    }
+
+    #[test]
+    fn test_distance_matrix_is_printable() {
+        let g = MatrixGraph::<u8, u32>::from_edges([(1, 2, 2), (2, 3, 3)]);
+        let paths = floyd_warshall(&g).unwrap();
+
+        let matrix = paths.distance_matrix();
+        assert_eq!(matrix.get_edge_by_index(0, 2), Some(&5));
+        assert_eq!(
+            matrix.to_string(),
+            "1 1\n2 2\n3 3\n#\n1 1 0\n1 2 2\n1 3 5\n2 2 0\n2 3 3\n3 3 0\n"
+        );
+    }
+
+    #[test]
+    fn test_returns_negative_cycle() {
+        let g = MatrixGraph::<u8, i32>::from_edges([(1, 2, 1), (2, 3, -3), (3, 2, 1)]);
+        let error = floyd_warshall(&g).unwrap_err();
+
+        let mut cycle = error.0.nodes().to_vec();
+        cycle.sort();
+        assert_eq!(cycle, vec![1, 2]);
+    }
+
+    fn assert_is_negative_cycle(g: &MatrixGraph<u8, i32>, cycle: &[usize]) {
+        let mut total = 0;
+        for (i, from) in cycle.iter().enumerate() {
+            total += g
+                .get_edge_by_index(*from, cycle[(i + 1) % cycle.len()])
+                .unwrap();
+        }
+        assert!(total < 0);
+
+        let mut unique = cycle.to_vec();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), cycle.len());
+    }
+
+    fn complete_graph<K: Copy>(size: u8, weight: K) -> MatrixGraph<u8, K> {
+        let mut g = MatrixGraph::default();
+        for node in 0..size {
+            g.add_node(node);
+        }
+        for from in 0..size as usize {
+            for to in (0..size as usize).filter(|to| *to != from) {
+                g.add_edge(from, to, weight);
+            }
+        }
+        g
+    }
+
+    #[test]
+    fn test_negative_cycle_in_complete_graph_does_not_overflow() {
+        let error = floyd_warshall(&complete_graph(12, -1i8)).unwrap_err();
+        assert_eq!(error.0.nodes().len(), 2);
+
+        let g = complete_graph(40, -1i32);
+        let error = floyd_warshall(&g).unwrap_err();
+        assert_is_negative_cycle(&g, error.0.nodes());
+    }
+
+    #[test]
+    fn test_returns_simple_negative_cycle() {
+        let g = MatrixGraph::<u8, i32>::from_edges([
+            (1, 2, 1),
+            (2, 3, 2),
+            (3, 4, -6),
+            (4, 2, 2),
+            (3, 1, 1),
+            (4, 5, 1),
+        ]);
+        let error = floyd_warshall(&g).unwrap_err();
+        assert_is_negative_cycle(&g, error.0.nodes());
+
+        let mut g = MatrixGraph::<u8, i32>::from_edges([(1, 2, 1)]);
+        g.add_edge(1, 1, -1);
+        assert_eq!(floyd_warshall(&g).unwrap_err().0.nodes(), &[1]);
+    }
+}
@@ -0,0 +1,114 @@
+//! Algorithms which run on any [`Graph`](crate::types::Graph)
+
+pub mod astar;
+pub mod bellman_ford;
+pub mod dijkstra;
+pub mod floyd_warshall;
+pub mod toposort;
+
+pub use astar::astar;
+pub use bellman_ford::bellman_ford;
+pub use dijkstra::{dijkstra, shortest_path};
+pub use floyd_warshall::{floyd_warshall, AllPairsShortestPaths};
+pub use toposort::{is_cyclic_directed, toposort, toposort_layers};
+
+use std::ops::Add;
+
+/// Boundary for edge weights used by shortest path algorithms
+pub trait Measure: Ord + Copy + Add<Output = Self> {
+    /// Returns weight of empty path
+    fn zero() -> Self;
+}
+
+macro_rules! impl_measure {
+    ($($t:ty),*) => {
+        $(
+            impl Measure for $t {
+                #[inline]
+                fn zero() -> Self {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_measure!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Distances and predecessors of nodes on the shortest paths from source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPaths<K> {
+    source: usize,
+    distances: Vec<Option<K>>,
+    predecessors: Vec<Option<usize>>,
+}
+
+impl<K> ShortestPaths<K>
+where
+    K: Measure,
+{
+    pub(crate) fn new(source: usize, bound: usize) -> Self {
+        let mut paths = Self {
+            source,
+            distances: vec![None; bound],
+            predecessors: vec![None; bound],
+        };
+        paths.distances[source] = Some(K::zero());
+        paths
+    }
+
+    /// Returns index of source node
+    #[inline]
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// Returns cost of the shortest path to node
+    /// or None if node is unreachable
+    #[inline]
+    pub fn distance(&self, node: usize) -> Option<K> {
+        *self.distances.get(node)?
+    }
+
+    /// Returns previous node on the shortest path to node,
+    /// None for source and unreachable nodes
+    #[inline]
+    pub fn predecessor(&self, node: usize) -> Option<usize> {
+        *self.predecessors.get(node)?
+    }
+
+    /// Returns distances indexed by node,
+    /// where unreachable nodes are None
+    #[inline]
+    pub fn distances(&self) -> &[Option<K>] {
+        &self.distances
+    }
+
+    /// Returns predecessors indexed by node
+    #[inline]
+    pub fn predecessors(&self) -> &[Option<usize>] {
+        &self.predecessors
+    }
+
+    /// Returns nodes of the shortest path from source to `to`
+    /// including both ends or None if `to` is unreachable
+    ///
+    /// Computes in **O(l)** where l = path length
+    pub fn path_to(&self, to: usize) -> Option<Vec<usize>> {
+        self.distance(to)?;
+
+        let mut path = vec![to];
+        while let Some(predecessor) = self.predecessor(*path.last()?) {
+            path.push(predecessor);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Sets distance to node reached by edge from `predecessor`
+    pub(crate) fn relax(&mut self, node: usize, predecessor: usize, distance: K) {
+        self.distances[node] = Some(distance);
+        self.predecessors[node] = Some(predecessor);
+    }
+}
@@ -0,0 +1,260 @@
+use crate::error::{Cycle, CycleError};
+use crate::traversable::{Control, DfsIterable, Visitor};
+use crate::types::{EdgeRef, Graph};
+use std::collections::VecDeque;
+
+/// Returns nodes in order where every node goes before it's neighbors
+/// using Kahn's algorithm, nodes without dependencies go by index
+///
+/// Returns [`CycleError`] with nodes of one of cycles if graph is not acyclic
+///
+/// Computes in **O(n + e)** where n = node count, e = edge count
+pub fn toposort<G>(graph: &G) -> Result<Vec<usize>, CycleError>
+where
+    G: Graph,
+{
+    let mut in_degrees = in_degrees(graph);
+    let mut queue = graph
+        .node_indices()
+        .filter(|idx| in_degrees[*idx] == 0)
+        .collect::<VecDeque<_>>();
+    let mut order = Vec::with_capacity(graph.node_count());
+
+    while let Some(node) = queue.pop_front() {
+        order.push(node);
+
+        for edge in graph.edges_from(node) {
+            in_degrees[edge.to] -= 1;
+            if in_degrees[edge.to] == 0 {
+                queue.push_back(edge.to);
+            }
+        }
+    }
+
+    if order.len() < graph.node_count() {
+        return Err(cycle_error(graph));
+    }
+
+    Ok(order)
+}
+
+/// Returns nodes grouped in layers, where every node depends
+/// only on nodes of previous layers, so nodes of one layer
+/// can be processed in parallel
+///
+/// Returns [`CycleError`] with nodes of one of cycles if graph is not acyclic
+///
+/// Computes in **O(n + e)** where n = node count, e = edge count
+pub fn toposort_layers<G>(graph: &G) -> Result<Vec<Vec<usize>>, CycleError>
+where
+    G: Graph,
+{
+    let mut in_degrees = in_degrees(graph);
+    let mut layer = graph
+        .node_indices()
+        .filter(|idx| in_degrees[*idx] == 0)
+        .collect::<Vec<_>>();
+    let mut layers = Vec::new();
+    let mut sorted = 0;
+
+    while !layer.is_empty() {
+        let mut next = Vec::new();
+
+        for &node in &layer {
+            for edge in graph.edges_from(node) {
+                in_degrees[edge.to] -= 1;
+                if in_degrees[edge.to] == 0 {
+                    next.push(edge.to);
+                }
+            }
+        }
+
+        next.sort_unstable();
+        sorted += layer.len();
+        layers.push(layer);
+        layer = next;
+    }
+
+    if sorted < graph.node_count() {
+        return Err(cycle_error(graph));
+    }
+
+    Ok(layers)
+}
+
+/// Returns true if graph has at least one directed cycle,
+/// including self loops
+///
+/// Computes in **O(n + e)** where n = node count, e = edge count
+pub fn is_cyclic_directed<G>(graph: &G) -> bool
+where
+    G: Graph,
+{
+    find_cycle(graph).is_some()
+}
+
+fn in_degrees<G>(graph: &G) -> Vec<usize>
+where
+    G: Graph,
+{
+    let mut in_degrees = vec![0; graph.node_bound()];
+    for node in graph.node_indices() {
+        for edge in graph.edges_from(node) {
+            in_degrees[edge.to] += 1;
+        }
+    }
+    in_degrees
+}
+
+fn cycle_error<G>(graph: &G) -> CycleError
+where
+    G: Graph,
+{
+    CycleError(Cycle::new(
+        find_cycle(graph).expect("Not sorted nodes contain cycle"),
+    ))
+}
+
+fn find_cycle<G>(graph: &G) -> Option<Vec<usize>>
+where
+    G: Graph,
+{
+    graph
+        .dfs_visit(graph.node_indices(), &mut CycleFinder::default())
+        .break_value()
+}
+
+/// Keeps path of nodes being traversed and breaks with it's part
+/// closed by the first back edge
+#[derive(Default)]
+struct CycleFinder {
+    path: Vec<usize>,
+}
+
+impl<T> Visitor<T> for CycleFinder {
+    type Break = Vec<usize>;
+
+    fn discover_node(&mut self, node: usize) -> Control<Self::Break> {
+        self.path.push(node);
+        Control::Continue
+    }
+
+    fn back_edge(&mut self, edge: EdgeRef<'_, T>) -> Control<Self::Break> {
+        let start = self
+            .path
+            .iter()
+            .rposition(|node| *node == edge.to)
+            .expect("Back edge leads to node on path");
+        Control::Break(self.path.split_off(start))
+    }
+
+    fn finish_node(&mut self, _node: usize) -> Control<Self::Break> {
+        self.path.pop();
+        Control::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix_graph::MatrixGraph;
+    use crate::types::GetNodeByIndex;
+
+    fn create_dag() -> MatrixGraph<&'static str, ()> {
+        MatrixGraph::from_edges([
+            ("fetch", "build", ()),
+            ("configure", "build", ()),
+            ("build", "test", ()),
+            ("build", "docs", ()),
+            ("test", "release", ()),
+            ("docs", "release", ()),
+        ])
+    }
+
+    fn assert_is_sorted(g: &MatrixGraph<&'static str, ()>, order: &[usize]) {
+        assert_eq!(order.len(), g.node_count());
+        for edge in g.edges() {
+            let position = |idx| order.iter().position(|node| *node == idx).unwrap();
+            assert!(position(edge.from) < position(edge.to));
+        }
+    }
+
+    #[test]
+    fn test_sorts_dag() {
+        let g = create_dag();
+        let order = toposort(&g).unwrap();
+
+        assert_is_sorted(&g, &order);
+        assert_eq!(g.get_node_by_index(order[0]), Some(&"fetch"));
+        assert_eq!(g.get_node_by_index(order[5]), Some(&"release"));
+        assert!(!is_cyclic_directed(&g));
+    }
+
+    #[test]
+    fn test_sorts_into_layers() {
+        let g = create_dag();
+        let names = toposort_layers(&g)
+            .unwrap()
+            .into_iter()
+            .map(|layer| {
+                layer
+                    .into_iter()
+                    .map(|idx| *g.get_node_by_index(idx).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            vec![
+                vec!["fetch", "configure"],
+                vec!["build"],
+                vec!["test", "docs"],
+                vec!["release"]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sorts_empty_and_removed_nodes() {
+        let mut g = create_dag();
+        assert_eq!(toposort(&MatrixGraph::<u8, ()>::default()), Ok(vec![]));
+
+        g.remove_node(g.get_index_of(&"build").unwrap());
+        let order = toposort(&g).unwrap();
+        assert_is_sorted(&g, &order);
+        assert_eq!(toposort_layers(&g).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_reports_cycle() {
+        let mut g = create_dag();
+        let (release, build) = (
+            g.get_index_of(&"release").unwrap(),
+            g.get_index_of(&"build").unwrap(),
+        );
+        g.add_edge(release, build, ());
+
+        let error = toposort(&g).unwrap_err();
+        let cycle = error.0.nodes();
+        assert!(cycle.len() == 3 && cycle.contains(&build) && cycle.contains(&release));
+        for (i, from) in cycle.iter().enumerate() {
+            assert!(g.contains_edge(*from, cycle[(i + 1) % cycle.len()]));
+        }
+
+        assert_eq!(toposort_layers(&g), Err(error));
+        assert!(is_cyclic_directed(&g));
+    }
+
+    #[test]
+    fn test_reports_self_loop() {
+        let mut g = MatrixGraph::<u8, ()>::from_edges([(1, 2, ())]);
+        g.add_edge(1, 1, ());
+
+        assert_eq!(toposort(&g), Err(CycleError(Cycle::new(vec![1]))));
+        assert_eq!(
+            CycleError(Cycle::new(vec![1])).to_string(),
+            "Cycle found: 1 1"
+        );
+    }
+}
@@ -0,0 +1,446 @@
+use crate::matrix_graph::{MatrixGraph, NodeIterator};
+use crate::node_storage::{DefaultBuildHasher, NodeStorage};
+use crate::types::{EdgeRef, GetEdgeByIndex, GetNodeByIndex, Graph, MatrixGraphNode, NodeBound};
+use std::hash::BuildHasher;
+use std::slice;
+
+/// Immutable graph representation in compressed sparse row format
+///
+/// Outgoing edges of node `i` are stored in
+/// `targets[offsets[i]..offsets[i + 1]]` sorted by target index,
+/// with weights in the same positions of `weights`
+///
+/// Note: it's better to use for graphs which are built once
+/// and then only queried
+pub struct CsrGraph<N, T, S = DefaultBuildHasher>
+where
+    N: MatrixGraphNode,
+{
+    nodes: NodeStorage<N, S>,
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<T>,
+}
+
+impl<N, T, S> CsrGraph<N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher + Default,
+{
+    /// Create CsrGraph from iterator of tuples
+    /// where each element representes edge between
+    /// two nodes and it's weight
+    ///
+    /// Computes in **O(n + e * log(e))**
+    ///
+    /// **Panics** if some edge repeats
+    pub fn from_edges(edges: impl IntoIterator<Item = (N, N, T)>) -> Self {
+        let mut nodes = NodeStorage::default();
+        let mut indexed = Vec::new();
+
+        for (from, to, weight) in edges {
+            let from_idx = nodes.get_or_insert(from);
+            let to_idx = nodes.get_or_insert(to);
+            indexed.push((from_idx, to_idx, weight));
+        }
+
+        indexed.sort_by_key(|(from, to, _)| (*from, *to));
+
+        for pair in indexed.windows(2) {
+            if (pair[0].0, pair[0].1) == (pair[1].0, pair[1].1) {
+                panic!("Edge from {} to {} already exists", pair[0].0, pair[0].1);
+            }
+        }
+
+        Self::from_sorted(nodes, indexed)
+    }
+}
+
+impl<N, T, S> From<MatrixGraph<N, T, S>> for CsrGraph<N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    /// Freezes MatrixGraph keeping indices of all nodes
+    ///
+    /// Computes in **O(n ^ 2)** where n = nodes count
+    fn from(graph: MatrixGraph<N, T, S>) -> Self {
+        let (nodes, adjacency, stride) = graph.into_parts();
+        let mut edges = Vec::new();
+
+        for (position, weight) in adjacency.into_iter().enumerate() {
+            if let Some(weight) = weight {
+                edges.push((position / stride, position % stride, weight));
+            }
+        }
+
+        Self::from_sorted(nodes, edges)
+    }
+}
+
+impl<N, T, S> CsrGraph<N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    /// Builds graph from edges sorted by source and target indices
+    fn from_sorted(nodes: NodeStorage<N, S>, edges: Vec<(usize, usize, T)>) -> Self {
+        let mut offsets = vec![0; nodes.bound() + 1];
+        let mut targets = Vec::with_capacity(edges.len());
+        let mut weights = Vec::with_capacity(edges.len());
+
+        for (from, to, weight) in edges {
+            offsets[from + 1] += 1;
+            targets.push(to);
+            weights.push(weight);
+        }
+
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+
+        Self {
+            nodes,
+            offsets,
+            targets,
+            weights,
+        }
+    }
+
+    /// Returns count of nodes
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns count of edges
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns upper bound of node indices
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn node_bound(&self) -> usize {
+        self.nodes.bound()
+    }
+
+    /// Returns iterator over nodes
+    #[inline]
+    pub fn node_iter(&self) -> NodeIterator<'_, N, S> {
+        NodeIterator::new(&self.nodes)
+    }
+
+    /// Checks if node exists in graph
+    ///
+    /// Computes in **O(1)** (average)
+    #[inline]
+    pub fn contains_node(&self, node: &N) -> bool {
+        self.nodes.contains(node)
+    }
+
+    /// Checks if edge between two nodes exists
+    ///
+    /// Computes in **O(log(d))** where d = outgoing edges count of `from`
+    #[inline]
+    pub fn contains_edge(&self, from: usize, to: usize) -> bool {
+        self.edge_position(from, to).is_some()
+    }
+
+    /// Returns index of node or None if not found
+    ///
+    /// Computes in **O(1)** (average)
+    #[inline]
+    pub fn get_index_of(&self, node: &N) -> Option<usize> {
+        self.nodes.get_index_of(node)
+    }
+
+    /// Returns outgoing edges count of node
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn degree(&self, node: usize) -> usize {
+        self.edge_range(node).len()
+    }
+
+    fn edge_range(&self, node: usize) -> std::ops::Range<usize> {
+        if node + 1 >= self.offsets.len() {
+            return 0..0;
+        }
+        self.offsets[node]..self.offsets[node + 1]
+    }
+
+    fn edge_position(&self, from: usize, to: usize) -> Option<usize> {
+        let range = self.edge_range(from);
+        let start = range.start;
+        self.targets[range]
+            .binary_search(&to)
+            .ok()
+            .map(|pos| start + pos)
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////////////
+
+impl<N, T, S> GetNodeByIndex<N> for CsrGraph<N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    #[inline]
+    fn get_node_by_index(&self, node_idx: usize) -> Option<&N> {
+        self.nodes.get_node_by_index(node_idx)
+    }
+}
+
+impl<N, T, S> NodeBound for CsrGraph<N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    #[inline]
+    fn node_bound(&self) -> usize {
+        self.nodes.bound()
+    }
+}
+
+impl<N, T, S> GetEdgeByIndex<T> for CsrGraph<N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    #[inline]
+    fn get_edge_by_index(&self, from: usize, to: usize) -> Option<&T> {
+        self.edge_position(from, to).map(|pos| &self.weights[pos])
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+pub struct CsrGraphNeighborsIterator<'a, N, S = DefaultBuildHasher>
+where
+    N: MatrixGraphNode,
+{
+    nodes: &'a NodeStorage<N, S>,
+    targets: slice::Iter<'a, usize>,
+}
+
+impl<'a, N, S> Iterator for CsrGraphNeighborsIterator<'a, N, S>
+where
+    N: MatrixGraphNode,
+{
+    type Item = (usize, &'a N);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.targets.next()?;
+        Some((*idx, self.nodes.get_node_by_index(*idx)?))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.targets.size_hint()
+    }
+}
+
+pub struct CsrGraphEdgesIterator<'a, T> {
+    from: usize,
+    targets: slice::Iter<'a, usize>,
+    weights: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for CsrGraphEdgesIterator<'a, T> {
+    type Item = EdgeRef<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(EdgeRef {
+            from: self.from,
+            to: *self.targets.next()?,
+            weight: self.weights.next()?,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.targets.size_hint()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl<N, T, S> Graph for CsrGraph<N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    type Node = N;
+    type Weight = T;
+    type Neighbors<'a>
+        = CsrGraphNeighborsIterator<'a, N, S>
+    where
+        Self: 'a;
+    type Edges<'a>
+        = CsrGraphEdgesIterator<'a, T>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        if self.nodes.get_node_by_index(node).is_none() {
+            panic!("Node with index {} not found", node);
+        }
+
+        CsrGraphNeighborsIterator {
+            nodes: &self.nodes,
+            targets: self.targets[self.edge_range(node)].iter(),
+        }
+    }
+
+    fn edges_from(&self, node: usize) -> Self::Edges<'_> {
+        let range = self.edge_range(node);
+
+        CsrGraphEdgesIterator {
+            from: node,
+            targets: self.targets[range.clone()].iter(),
+            weights: self.weights[range].iter(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialization::ser_tgf;
+    use crate::traversable::BfsIterable;
+    use crate::types::{Adjacency, GraphEntry};
+    use std::collections::hash_map::RandomState;
+
+    const EDGES: [(u32, u32, u8); 8] = [
+        (1, 2, 3),
+        (3, 4, 7),
+        (1, 3, 4),
+        (3, 2, 5),
+        (5, 2, 7),
+        (1, 4, 5),
+        (1, 5, 6),
+        (3, 1, 4),
+    ];
+
+    #[test]
+    fn test_creates_from_edges() {
+        let g = CsrGraph::<u32, u8>::from_edges(EDGES);
+        assert_eq!(g.node_count(), 5);
+        assert_eq!(g.edge_count(), EDGES.len());
+
+        for (from, to, weight) in EDGES {
+            let from_idx = g.get_index_of(&from).unwrap();
+            let to_idx = g.get_index_of(&to).unwrap();
+            assert_eq!(g.get_edge_by_index(from_idx, to_idx).unwrap(), &weight);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Edge from 0 to 1 already exists")]
+    fn test_panics_on_repeated_edge() {
+        CsrGraph::<u32, u8>::from_edges([(1, 2, 3), (1, 2, 4)]);
+    }
+
+    #[test]
+    fn test_creates_from_matrix_graph() {
+        let mut m = MatrixGraph::<u32, u8>::from_edges(EDGES);
+        m.remove_node(1);
+        let expected = ser_tgf(&m.get_adjacency_matrix());
+
+        let g = CsrGraph::from(m);
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(g.edge_count(), 5);
+        assert!(g.get_node_by_index(1).is_none());
+        assert_eq!(expected, ser_tgf(&g));
+    }
+
+    #[test]
+    fn test_creates_from_matrix_graph_with_hasher() {
+        let mut m = MatrixGraph::<u32, u8, RandomState>::with_hasher(RandomState::new());
+        let a = m.add_node(1);
+        let b = m.add_node(2);
+        m.add_edge(a, b, 3);
+
+        let g = CsrGraph::from(m);
+        assert_eq!(g.get_index_of(&2), Some(b));
+        assert_eq!(g.get_edge_by_index(a, b), Some(&3));
+    }
+
+    #[test]
+    fn test_neighbors_are_sorted_by_index() {
+        let g = CsrGraph::<u32, u8>::from_edges(EDGES);
+        let actual = g.neighbors(0).map(|(idx, _)| idx).collect::<Vec<_>>();
+        assert_eq!(actual, vec![1, 2, 3, 4]);
+        assert_eq!(g.degree(0), 4);
+    }
+
+    #[test]
+    fn test_edges_from_are_sorted_by_target() {
+        let g = CsrGraph::<u32, u8>::from_edges(EDGES);
+        let actual = g
+            .edges_from(2)
+            .map(|edge| (edge.to, *edge.weight))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![(0, 4), (1, 5), (3, 7)]);
+    }
+
+    #[test]
+    fn test_contains_edge() {
+        let g = CsrGraph::<u32, u8>::from_edges(EDGES);
+        assert!(g.contains_edge(0, 1));
+        assert!(!g.contains_edge(1, 0));
+        assert!(!g.contains_edge(10, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Node with index 6 not found")]
+    fn test_panics_on_getting_neighbors_for_not_existed_node() {
+        let g = CsrGraph::<u32, u8>::from_edges(EDGES);
+        g.neighbors(6);
+    }
+
+    #[test]
+    fn test_bfs_iter() {
+        let g = CsrGraph::<u8, ()>::from_edges([(1, 4, ()), (1, 2, ()), (4, 1, ()), (2, 3, ())]);
+
+        let actual = g.bfs_iter(0).collect::<Vec<_>>();
+        let expected = vec![
+            GraphEntry {
+                node: &1,
+                edges: vec![&4, &2],
+            },
+            GraphEntry {
+                node: &4,
+                edges: vec![&1],
+            },
+            GraphEntry {
+                node: &2,
+                edges: vec![&3],
+            },
+            GraphEntry {
+                node: &3,
+                edges: vec![],
+            },
+        ];
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,90 @@
+use std::error::Error;
+use std::fmt;
+
+/// Error of graph mutation or lookup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphError {
+    /// Node equal to added one already exists
+    DuplicateNode,
+    /// Edge between two nodes already exists
+    DuplicateEdge(usize, usize),
+    /// Node index is out of graph bounds
+    NodeNotFound(usize),
+    /// Node with index was removed
+    RemovedNode(usize),
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::GraphError::*;
+
+        let output = match self {
+            DuplicateNode => "Nodes should be unique.".to_string(),
+            DuplicateEdge(from, to) => format!("Edge from {} to {} already exists", from, to),
+            NodeNotFound(idx) => format!("Node with index {} not found", idx),
+            RemovedNode(idx) => format!("Node with index {} was removed", idx),
+        };
+
+        write!(f, "{}", output)?;
+
+        Ok(())
+    }
+}
+
+impl Error for GraphError {}
+
+/// Node indices of cycle in edge order,
+/// last node has edge to the first one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle(Vec<usize>);
+
+impl Cycle {
+    pub(crate) fn new(nodes: Vec<usize>) -> Self {
+        Self(nodes)
+    }
+
+    #[inline]
+    pub fn nodes(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+/// Prints nodes of cycle, repeating the first one at the end
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut nodes = self.0.iter().chain(self.0.first());
+
+        if let Some(first) = nodes.next() {
+            write!(f, "{}", first)?;
+        }
+        for idx in nodes {
+            write!(f, " {}", idx)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Error of shortest path search when reachable cycle has negative total weight
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycle(pub Cycle);
+
+impl fmt::Display for NegativeCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Negative cycle found: {}", self.0)
+    }
+}
+
+impl Error for NegativeCycle {}
+
+/// Error of topological sort when graph contains cycle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError(pub Cycle);
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cycle found: {}", self.0)
+    }
+}
+
+impl Error for CycleError {}
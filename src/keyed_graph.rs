@@ -0,0 +1,432 @@
+use crate::matrix_graph::NodeIterator;
+use crate::matrix_graph::{MatrixGraph, MatrixGraphEdgesIterator};
+use crate::matrix_graph::{MatrixGraphNeighborsIterator, MatrixGraphPredecessorsIterator};
+use crate::node_storage::DefaultBuildHasher;
+use crate::types::{GetEdgeByIndex, GetNodeByIndex, Graph, IncomingNeighbors};
+use crate::types::{MatrixGraphNode, NodeBound};
+use std::hash::BuildHasher;
+
+/// Graph representation with adjacency matrix
+/// where each node is identified by key `K`
+/// and carries payload `D`
+///
+/// Only key has to be `Eq + Hash`, so payload can hold
+/// any data and can be changed in place
+pub struct KeyedGraph<K, D, T, S = DefaultBuildHasher>
+where
+    K: MatrixGraphNode,
+{
+    graph: MatrixGraph<K, T, S>,
+    payloads: Vec<Option<D>>,
+}
+
+impl<K, D, T, S> Default for KeyedGraph<K, D, T, S>
+where
+    K: MatrixGraphNode,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        Self {
+            graph: MatrixGraph::default(),
+            payloads: Vec::new(),
+        }
+    }
+}
+
+impl<K, D, T, S> KeyedGraph<K, D, T, S>
+where
+    K: MatrixGraphNode,
+    D: Default,
+    S: BuildHasher + Default,
+{
+    /// Create KeyedGraph from iterator of tuples
+    /// where each element representes edge between
+    /// two keys and it's weight
+    ///
+    /// Payloads of nodes are set to default
+    pub fn from_edges(edges: impl IntoIterator<Item = (K, K, T)>) -> Self {
+        let mut g = Self::default();
+
+        for (from, to, weight) in edges {
+            let from_idx = g.key_or_insert(from);
+            let to_idx = g.key_or_insert(to);
+            g.add_edge(from_idx, to_idx, weight);
+        }
+
+        g
+    }
+
+    fn key_or_insert(&mut self, key: K) -> usize {
+        let payloads = &mut self.payloads;
+        self.graph
+            .node_entry(key)
+            .or_insert_with(|idx| Self::set_payload(payloads, idx, D::default()))
+    }
+}
+
+impl<K, D, T, S> KeyedGraph<K, D, T, S>
+where
+    K: MatrixGraphNode,
+    S: BuildHasher,
+{
+    /// Adds new node with payload in graph
+    ///
+    /// Returns index of new node
+    ///
+    /// Computes in **O(1)** (average amortized)
+    /// Worse case **O(n ^ 2)** where n is nodes count
+    ///
+    /// **Panics** if node with same key already exists
+    pub fn add_node(&mut self, key: K, payload: D) -> usize {
+        let idx = self.graph.add_node(key);
+        Self::set_payload(&mut self.payloads, idx, payload);
+        idx
+    }
+
+    /// Removes node and all edges for it
+    ///
+    /// Returns removed key and payload or None, if node not found
+    ///
+    /// Computes in **O(n)** where n = nodes count
+    pub fn remove_node(&mut self, node_index: usize) -> Option<(K, D)> {
+        let key = self.graph.remove_node(node_index)?;
+        let payload = self.payloads[node_index]
+            .take()
+            .expect("Payload of existing node should exist");
+        Some((key, payload))
+    }
+
+    /// Adds edge between two nodes
+    ///
+    /// Computes in **O(1)**
+    ///
+    /// **Panics** if some of nodes not exists or edge already exists
+    #[inline]
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: T) {
+        self.graph.add_edge(from, to, weight)
+    }
+
+    /// Removes edge between two nodes
+    ///
+    /// Returns edge's weight if removed else None
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> Option<T> {
+        self.graph.remove_edge(from, to)
+    }
+
+    /// Returns count of nodes
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /// Returns count of edges
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    /// Returns upper bound of node indices
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn node_bound(&self) -> usize {
+        self.graph.node_bound()
+    }
+
+    /// Returns iterator over keys
+    #[inline]
+    pub fn node_iter(&mut self) -> NodeIterator<'_, K, S> {
+        self.graph.node_iter()
+    }
+
+    /// Checks if node with key exists in graph
+    ///
+    /// Computes in **O(1)** (average)
+    #[inline]
+    pub fn contains_node(&self, key: &K) -> bool {
+        self.graph.contains_node(key)
+    }
+
+    /// Checks if edge between two nodes exists
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn contains_edge(&self, from: usize, to: usize) -> bool {
+        self.graph.contains_edge(from, to)
+    }
+
+    /// Returns index of node with key or None if not found
+    ///
+    /// Computes in **O(1)** (average)
+    #[inline]
+    pub fn get_index_of(&self, key: &K) -> Option<usize> {
+        self.graph.get_index_of(key)
+    }
+
+    /// Returns payload of node with key or None if not found
+    ///
+    /// Computes in **O(1)** (average)
+    pub fn get_by_key(&self, key: &K) -> Option<&D> {
+        self.payload(self.get_index_of(key)?)
+    }
+
+    /// Returns mutable payload of node with key or None if not found
+    ///
+    /// Computes in **O(1)** (average)
+    pub fn get_by_key_mut(&mut self, key: &K) -> Option<&mut D> {
+        let idx = self.get_index_of(key)?;
+        self.payload_mut(idx)
+    }
+
+    /// Returns payload of node or None if node not exists
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn payload(&self, node_index: usize) -> Option<&D> {
+        self.payloads.get(node_index)?.as_ref()
+    }
+
+    /// Returns mutable payload of node or None if node not exists
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn payload_mut(&mut self, node_index: usize) -> Option<&mut D> {
+        self.payloads.get_mut(node_index)?.as_mut()
+    }
+
+    /// Returns mutable reference to edge's weight
+    /// or None if edge not exists
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn get_edge_mut(&mut self, from: usize, to: usize) -> Option<&mut T> {
+        self.graph.get_edge_mut(from, to)
+    }
+
+    fn set_payload(payloads: &mut Vec<Option<D>>, idx: usize, payload: D) {
+        if idx >= payloads.len() {
+            payloads.resize_with(idx + 1, || None);
+        }
+        payloads[idx] = Some(payload);
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////////////
+
+impl<K, D, T, S> GetNodeByIndex<K> for KeyedGraph<K, D, T, S>
+where
+    K: MatrixGraphNode,
+    S: BuildHasher,
+{
+    #[inline]
+    fn get_node_by_index(&self, node_idx: usize) -> Option<&K> {
+        self.graph.get_node_by_index(node_idx)
+    }
+}
+
+impl<K, D, T, S> NodeBound for KeyedGraph<K, D, T, S>
+where
+    K: MatrixGraphNode,
+    S: BuildHasher,
+{
+    #[inline]
+    fn node_bound(&self) -> usize {
+        self.graph.node_bound()
+    }
+}
+
+impl<K, D, T, S> GetEdgeByIndex<T> for KeyedGraph<K, D, T, S>
+where
+    K: MatrixGraphNode,
+    S: BuildHasher,
+{
+    #[inline]
+    fn get_edge_by_index(&self, from: usize, to: usize) -> Option<&T> {
+        self.graph.get_edge_by_index(from, to)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl<K, D, T, S> Graph for KeyedGraph<K, D, T, S>
+where
+    K: MatrixGraphNode,
+    S: BuildHasher,
+{
+    type Node = K;
+    type Weight = T;
+    type Neighbors<'a>
+        = MatrixGraphNeighborsIterator<'a, K, T, S>
+    where
+        Self: 'a;
+    type Edges<'a>
+        = MatrixGraphEdgesIterator<'a, T>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    #[inline]
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    #[inline]
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        self.graph.neighbors(node)
+    }
+
+    #[inline]
+    fn edges_from(&self, node: usize) -> Self::Edges<'_> {
+        self.graph.edges_from(node)
+    }
+}
+
+impl<K, D, T, S> IncomingNeighbors for KeyedGraph<K, D, T, S>
+where
+    K: MatrixGraphNode,
+    S: BuildHasher,
+{
+    type Predecessors<'a>
+        = MatrixGraphPredecessorsIterator<'a, K, T, S>
+    where
+        Self: 'a;
+    type IncomingEdges<'a>
+        = MatrixGraphEdgesIterator<'a, T>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn predecessors(&self, node: usize) -> Self::Predecessors<'_> {
+        self.graph.predecessors(node)
+    }
+
+    #[inline]
+    fn edges_to(&self, node: usize) -> Self::IncomingEdges<'_> {
+        self.graph.edges_to(node)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialization::ser_tgf;
+    use crate::traversable::BfsIterable;
+    use crate::types::GraphEntry;
+
+    /// Payload which is neither `Eq` nor `Hash`
+    #[derive(Debug, Default, PartialEq)]
+    struct City {
+        population: f64,
+        districts: Vec<String>,
+    }
+
+    fn create_graph() -> KeyedGraph<&'static str, City, u32> {
+        KeyedGraph::default()
+    }
+
+    #[test]
+    fn test_creates_default_empty() {
+        let g = create_graph();
+        assert_eq!(g.node_count(), 0);
+        assert_eq!(g.edge_count(), 0);
+    }
+
+    #[test]
+    fn test_gets_payload_by_key() {
+        let mut g = create_graph();
+        let city = City {
+            population: 1.5,
+            districts: vec!["north".to_string()],
+        };
+        let idx = g.add_node("A", city);
+
+        assert_eq!(g.get_index_of(&"A"), Some(idx));
+        assert_eq!(g.get_by_key(&"A").unwrap().population, 1.5);
+        assert_eq!(g.payload(idx).unwrap().districts, vec!["north"]);
+        assert!(g.get_by_key(&"B").is_none());
+    }
+
+    #[test]
+    fn test_changes_payload_in_place() {
+        let mut g = create_graph();
+        let idx = g.add_node("A", City::default());
+
+        g.payload_mut(idx).unwrap().population = 2.0;
+        g.get_by_key_mut(&"A")
+            .unwrap()
+            .districts
+            .push("south".to_string());
+
+        let city = g.get_by_key(&"A").unwrap();
+        assert_eq!(city.population, 2.0);
+        assert_eq!(city.districts, vec!["south"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Nodes should be unique.")]
+    fn test_panics_on_adding_same_key() {
+        let mut g = create_graph();
+        g.add_node("A", City::default());
+        g.add_node("A", City::default());
+    }
+
+    #[test]
+    fn test_removes_node_with_payload() {
+        let mut g = KeyedGraph::<u32, Vec<u8>, ()>::from_edges([(1, 2, ()), (2, 3, ())]);
+        g.payload_mut(1).unwrap().push(7);
+
+        assert_eq!(g.remove_node(1), Some((2, vec![7])));
+        assert_eq!(g.edge_count(), 0);
+        assert!(g.payload(1).is_none());
+
+        let idx = g.add_node(4, vec![1]);
+        assert_eq!(idx, 1);
+        assert_eq!(g.payload(idx), Some(&vec![1]));
+    }
+
+    #[test]
+    fn test_bfs_iter_yields_keys() {
+        let g = KeyedGraph::<u8, f32, ()>::from_edges([(1, 2, ()), (1, 3, ()), (3, 4, ())]);
+
+        let actual = g.bfs_iter(0).collect::<Vec<_>>();
+        let expected = vec![
+            GraphEntry {
+                node: &1,
+                edges: vec![&2, &3],
+            },
+            GraphEntry {
+                node: &2,
+                edges: vec![],
+            },
+            GraphEntry {
+                node: &3,
+                edges: vec![&4],
+            },
+            GraphEntry {
+                node: &4,
+                edges: vec![],
+            },
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_ser_to_tgf() {
+        let g = KeyedGraph::<u32, Vec<f64>, u8>::from_edges([(1, 2, 3), (2, 3, 4)]);
+        assert_eq!("1 1\n2 2\n3 3\n#\n1 2 3\n2 3 4\n", ser_tgf(&g));
+    }
+}
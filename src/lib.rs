@@ -1,5 +1,12 @@
+pub mod algo;
+pub mod csr_graph;
+pub mod error;
+pub mod keyed_graph;
+pub mod list_graph;
 pub mod matrix_graph;
+pub mod multi_graph;
 pub mod node_storage;
 pub mod serialization;
 pub mod traversable;
 pub mod types;
+pub mod undirected_matrix_graph;
@@ -0,0 +1,453 @@
+use crate::matrix_graph::NodeIterator;
+use crate::node_storage::NodeStorage;
+use crate::types::{EdgeRef, GetEdgeByIndex, GetNodeByIndex, Graph, MatrixGraphNode, NodeBound};
+use std::slice;
+
+/// Graph representation with adjacency lists
+///
+/// Note: it's better to use for sparse graph
+pub struct ListGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    nodes: NodeStorage<N>,
+    adjacency: Vec<Vec<(usize, T)>>,
+    edge_count: usize,
+}
+
+impl<N, T> Default for ListGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    fn default() -> Self {
+        Self {
+            nodes: NodeStorage::default(),
+            adjacency: Vec::new(),
+            edge_count: 0,
+        }
+    }
+}
+
+impl<N, T> ListGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    /// Create ListGraph from iterator of tuples
+    /// where each element representes edge between
+    /// two nodes and it's weight
+    pub fn from_edges(edges: impl IntoIterator<Item = (N, N, T)>) -> Self {
+        let mut g = Self::default();
+
+        for (from, to, weight) in edges {
+            let from_idx = g.nodes.get_or_insert(from);
+            let to_idx = g.nodes.get_or_insert(to);
+            g.adjacency.resize_with(g.nodes.bound(), Vec::new);
+            g.add_edge(from_idx, to_idx, weight);
+        }
+
+        g
+    }
+}
+
+impl<N, T> ListGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    /// Adds new node in graph
+    ///
+    /// Returns index of new node
+    ///
+    /// Computes in **O(1)** (average amortized)
+    ///
+    /// **Panics** if node already exists
+    pub fn add_node(&mut self, node: N) -> usize {
+        let idx = self.nodes.add(node);
+
+        if idx >= self.adjacency.len() {
+            self.adjacency.resize_with(idx + 1, Vec::new);
+        }
+
+        idx
+    }
+
+    /// Removes node and all edges for it
+    ///
+    /// Returns removed node or None, if node not found
+    ///
+    /// Computes in **O(n + e)** where n = nodes count, e = edges count
+    pub fn remove_node(&mut self, node_index: usize) -> Option<N> {
+        self.nodes.get_node_by_index(node_index)?;
+
+        self.edge_count -= self.adjacency[node_index].len();
+        self.adjacency[node_index].clear();
+
+        for edges in self.adjacency.iter_mut() {
+            let before = edges.len();
+            edges.retain(|(to, _)| *to != node_index);
+            self.edge_count -= before - edges.len();
+        }
+
+        self.nodes.remove(node_index)
+    }
+
+    /// Adds edge between two nodes
+    ///
+    /// Computes in **O(d)** where d = outgoing edges count of `from`
+    ///
+    /// **Panics** if some of nodes not exists or edge already exists
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: T) {
+        let missing = [from, to]
+            .into_iter()
+            .filter(|idx| self.nodes.get_node_by_index(*idx).is_none())
+            .max();
+
+        if let Some(idx) = missing {
+            panic!("Can't add edge for not existing node with index {}", idx);
+        }
+
+        if self.contains_edge(from, to) {
+            panic!("Edge from {} to {} already exists", from, to);
+        }
+
+        self.adjacency[from].push((to, weight));
+        self.edge_count += 1;
+    }
+
+    /// Removes edge between two nodes
+    ///
+    /// Returns edge's weight if removed else None
+    ///
+    /// Computes in **O(d)** where d = outgoing edges count of `from_node`
+    pub fn remove_edge(&mut self, from_node: usize, to_node: usize) -> Option<T> {
+        let edges = self.adjacency.get_mut(from_node)?;
+        let position = edges.iter().position(|(to, _)| *to == to_node)?;
+        self.edge_count -= 1;
+        Some(edges.swap_remove(position).1)
+    }
+
+    /// Returns count of nodes
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns count of edges
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Returns upper bound of node indices
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn node_bound(&self) -> usize {
+        self.nodes.bound()
+    }
+
+    /// Returns iterator over nodes
+    #[inline]
+    pub fn node_iter(&self) -> NodeIterator<'_, N> {
+        NodeIterator::new(&self.nodes)
+    }
+
+    /// Checks if node exists in graph
+    ///
+    /// Computes in **O(1)** (average)
+    #[inline]
+    pub fn contains_node(&self, node: &N) -> bool {
+        self.nodes.contains(node)
+    }
+
+    /// Checks if edge between two nodes exists
+    ///
+    /// Computes in **O(d)** where d = outgoing edges count of `from`
+    #[inline]
+    pub fn contains_edge(&self, from: usize, to: usize) -> bool {
+        self.get_edge_by_index(from, to).is_some()
+    }
+
+    /// Returns index of node or None if not found
+    ///
+    /// Computes in **O(1)** (average)
+    #[inline]
+    pub fn get_index_of(&self, node: &N) -> Option<usize> {
+        self.nodes.get_index_of(node)
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////////////
+
+impl<N, T> GetNodeByIndex<N> for ListGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    #[inline]
+    fn get_node_by_index(&self, node_idx: usize) -> Option<&N> {
+        self.nodes.get_node_by_index(node_idx)
+    }
+}
+
+impl<N, T> NodeBound for ListGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    #[inline]
+    fn node_bound(&self) -> usize {
+        self.nodes.bound()
+    }
+}
+
+impl<N, T> GetEdgeByIndex<T> for ListGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    #[inline]
+    fn get_edge_by_index(&self, from: usize, to: usize) -> Option<&T> {
+        self.adjacency
+            .get(from)?
+            .iter()
+            .find(|(idx, _)| *idx == to)
+            .map(|(_, weight)| weight)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+pub struct ListGraphNeighborsIterator<'a, N, T>
+where
+    N: MatrixGraphNode,
+{
+    nodes: &'a NodeStorage<N>,
+    edges: slice::Iter<'a, (usize, T)>,
+}
+
+impl<'a, N, T> Iterator for ListGraphNeighborsIterator<'a, N, T>
+where
+    N: MatrixGraphNode,
+{
+    type Item = (usize, &'a N);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (idx, _) = self.edges.next()?;
+        Some((*idx, self.nodes.get_node_by_index(*idx)?))
+    }
+}
+
+pub struct ListGraphEdgesIterator<'a, T> {
+    from: usize,
+    edges: slice::Iter<'a, (usize, T)>,
+}
+
+impl<'a, T> Iterator for ListGraphEdgesIterator<'a, T> {
+    type Item = EdgeRef<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (to, weight) = self.edges.next()?;
+        Some(EdgeRef {
+            from: self.from,
+            to: *to,
+            weight,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl<N, T> Graph for ListGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    type Node = N;
+    type Weight = T;
+    type Neighbors<'a>
+        = ListGraphNeighborsIterator<'a, N, T>
+    where
+        Self: 'a;
+    type Edges<'a>
+        = ListGraphEdgesIterator<'a, T>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        if self.nodes.get_node_by_index(node).is_none() {
+            panic!("Node with index {} not found", node);
+        }
+
+        ListGraphNeighborsIterator {
+            nodes: &self.nodes,
+            edges: self.adjacency[node].iter(),
+        }
+    }
+
+    fn edges_from(&self, node: usize) -> Self::Edges<'_> {
+        let edges = match self.adjacency.get(node) {
+            Some(edges) => edges.iter(),
+            None => [].iter(),
+        };
+
+        ListGraphEdgesIterator { from: node, edges }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialization::ser_tgf;
+    use crate::traversable::BfsIterable;
+    use crate::types::GraphEntry;
+
+    fn create_graph() -> ListGraph<u32, ()> {
+        ListGraph::<u32, ()>::default()
+    }
+
+    #[test]
+    fn test_creates_default_empty() {
+        let g = create_graph();
+        assert_eq!(g.node_count(), 0);
+        assert_eq!(g.edge_count(), 0);
+    }
+
+    #[test]
+    fn test_creates_from_edges() {
+        let edges = [(1, 2, 3), (3, 4, 7), (1, 3, 4), (3, 2, 5), (5, 2, 7)];
+
+        let g = ListGraph::<u32, u8>::from_edges(edges);
+        assert_eq!(g.node_count(), 5);
+        assert_eq!(g.edge_count(), edges.len());
+
+        for (from, to, weight) in edges {
+            let from_idx = g.get_index_of(&from).unwrap();
+            let to_idx = g.get_index_of(&to).unwrap();
+            assert_eq!(g.get_edge_by_index(from_idx, to_idx).unwrap(), &weight);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Edge from 0 to 1 already exists")]
+    fn test_panics_on_creating_existing_edge() {
+        let mut g = create_graph();
+        let first = g.add_node(34);
+        let second = g.add_node(52);
+        g.add_edge(first, second, ());
+        g.add_edge(first, second, ());
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't add edge for not existing node with index 1")]
+    fn test_panics_on_create_edge_for_not_existing_node() {
+        let mut g = create_graph();
+        g.add_edge(0, 1, ());
+    }
+
+    #[test]
+    fn test_removes_node_with_incoming_and_outgoing_edges() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        let c = g.add_node(3);
+        g.add_edge(a, b, ());
+        g.add_edge(b, c, ());
+        g.add_edge(c, a, ());
+
+        assert_eq!(g.remove_node(b), Some(2));
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.edge_count(), 1);
+        assert!(!g.contains_edge(a, b));
+        assert!(g.contains_edge(c, a));
+    }
+
+    #[test]
+    fn test_reuses_removed_slot_without_stale_edges() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        g.add_edge(b, a, ());
+        g.remove_node(b);
+
+        let c = g.add_node(3);
+        assert_eq!(c, b);
+        assert_eq!(g.neighbors(c).count(), 0);
+    }
+
+    #[test]
+    fn test_removes_existing_edge() {
+        let mut g = create_graph();
+        let a = g.add_node(12);
+        let b = g.add_node(54);
+        g.add_edge(a, b, ());
+        assert_eq!(g.remove_edge(a, b), Some(()));
+        assert_eq!(g.remove_edge(a, b), None);
+        assert_eq!(g.edge_count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Node with index 6 not found")]
+    fn test_panics_on_getting_neighbors_for_not_existed_node() {
+        let g = create_graph();
+        g.neighbors(6);
+    }
+
+    #[test]
+    fn test_bfs_iter() {
+        let g = ListGraph::<u8, ()>::from_edges([(1, 4, ()), (1, 2, ()), (4, 1, ()), (2, 3, ())]);
+
+        let actual = g.bfs_iter(0).collect::<Vec<_>>();
+        let expected = vec![
+            GraphEntry {
+                node: &1,
+                edges: vec![&4, &2],
+            },
+            GraphEntry {
+                node: &4,
+                edges: vec![&1],
+            },
+            GraphEntry {
+                node: &2,
+                edges: vec![&3],
+            },
+            GraphEntry {
+                node: &3,
+                edges: vec![],
+            },
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_edges_from_yields_weights() {
+        let g = ListGraph::<u32, u8>::from_edges([(1, 2, 3), (1, 3, 4), (3, 1, 5)]);
+
+        let actual = g
+            .edges_from(0)
+            .map(|edge| (edge.from, edge.to, *edge.weight))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![(0, 1, 3), (0, 2, 4)]);
+        assert_eq!(g.edges_from(10).count(), 0);
+    }
+
+    #[test]
+    fn test_ser_to_tgf() {
+        let mut g = ListGraph::<u32, u8>::from_edges([(1, 2, 3), (2, 3, 4), (3, 1, 5)]);
+        g.remove_node(0);
+
+        assert_eq!("1 2\n2 3\n#\n1 2 4\n", ser_tgf(&g));
+    }
+}
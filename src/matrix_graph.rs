@@ -1,38 +1,73 @@
-use crate::node_storage::NodeStorage;
+use crate::error::GraphError;
+use crate::node_storage::{DefaultBuildHasher, NodeMut, NodeStorage};
 use crate::traversable::BfsIterable;
-use crate::types::{Adjacency, AdjacencyMatrix, GetEdgeByIndex, GetNodeByIndex};
-use crate::types::{IteratorHandle, MatrixGraphNode, Neighbors};
-use std::{cmp, fmt, mem, vec};
+use crate::types::{Adjacency, AdjacencyMatrix, EdgeRef, GetEdgeByIndex, GetNodeByIndex};
+use crate::types::{Graph, IncomingNeighbors, MatrixGraphNode, NodeBound, NodeIndex};
+use std::hash::BuildHasher;
+use std::iter::StepBy;
+use std::ops::Range;
+use std::{cmp, fmt, mem};
 
 /// Graph representation with adjacency matrix
 ///
+/// Matrix is stored in single contiguous vector,
+/// where edge `(from, to)` is at `from * capacity + to`
+///
 /// Note: it's better to use for dense graph
-pub struct MatrixGraph<N, T>
+pub struct MatrixGraph<N, T, S = DefaultBuildHasher>
 where
     N: MatrixGraphNode,
 {
-    nodes: NodeStorage<N>,
-    adjacency: Vec<Vec<Option<T>>>,
+    nodes: NodeStorage<N, S>,
+    adjacency: Vec<Option<T>>,
+    capacity: usize,
     edge_count: usize,
 }
 
-impl<N, T> Default for MatrixGraph<N, T>
+impl<N, T, S> Default for MatrixGraph<N, T, S>
 where
     N: MatrixGraphNode,
+    S: BuildHasher + Default,
 {
     fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<N, T, S> MatrixGraph<N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    /// Create empty MatrixGraph which uses given hasher for nodes
+    pub fn with_hasher(hasher: S) -> Self {
+        Self::with_capacity_and_hasher(0, hasher)
+    }
+
+    /// Create empty MatrixGraph with space for at least `capacity` nodes
+    /// which uses given hasher for nodes
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
         Self {
-            nodes: NodeStorage::default(),
-            adjacency: vec![vec![None]],
+            nodes: NodeStorage::with_hasher(hasher),
+            adjacency: Self::empty_matrix(capacity),
+            capacity,
             edge_count: 0,
         }
     }
 }
 
-impl<N, T> MatrixGraph<N, T>
+impl<N, T, S> MatrixGraph<N, T, S>
 where
     N: MatrixGraphNode,
+    S: BuildHasher + Default,
 {
+    /// Create empty MatrixGraph with space for at least `capacity` nodes
+    ///
+    /// Takes **O(c ^ 2)** space where c = capacity
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, S::default())
+    }
+
     /// Create MatrixGraph from iterator of tuples
     /// where each element representes edge between
     /// two nodes and it's weight
@@ -40,16 +75,8 @@ where
         let mut g = Self::default();
 
         for (from, to, weight) in edges {
-            let from_idx = match g.contains_node(&from) {
-                true => g.get_index_of(&from).unwrap(),
-                false => g.add_node(from),
-            };
-
-            let to_idx = match g.contains_node(&to) {
-                true => g.get_index_of(&to).unwrap(),
-                false => g.add_node(to),
-            };
-
+            let from_idx = g.node_entry(from).or_insert();
+            let to_idx = g.node_entry(to).or_insert();
             g.add_edge(from_idx, to_idx, weight);
         }
 
@@ -57,42 +84,63 @@ where
     }
 }
 
-impl<N, T> MatrixGraph<N, T>
+impl<N, T, S> MatrixGraph<N, T, S>
 where
     N: MatrixGraphNode,
+    S: BuildHasher,
 {
     /// Adds new node in graph
     ///
     /// Returns index of new node
     ///
     /// Computes in **O(1)** (average amortized)
-    /// Worse case **O(n)** where n is nodes count
+    /// Worse case **O(n ^ 2)** where n is nodes count
     ///
     /// **Panics** if node already exists
     pub fn add_node(&mut self, node: N) -> usize {
-        self.nodes.add(node)
+        self.try_add_node(node)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Adds new node in graph
+    ///
+    /// Returns index of new node or error if node already exists
+    ///
+    /// Computes in **O(1)** (average amortized)
+    /// Worse case **O(n ^ 2)** where n is nodes count
+    pub fn try_add_node(&mut self, node: N) -> Result<usize, GraphError> {
+        let idx = self.nodes.try_add(node)?;
+        self.grow_for(idx);
+        Ok(idx)
+    }
+
+    /// Returns entry of node for in-place lookup or insertion
+    ///
+    /// Node is hashed only once whether it exists or not
+    ///
+    /// Computes in **O(1)** (average)
+    pub fn node_entry(&mut self, node: N) -> Entry<'_, N, T, S> {
+        match self.nodes.lookup(&node) {
+            Ok(index) => Entry::Occupied(index),
+            Err(hash) => Entry::Vacant(VacantEntry {
+                graph: self,
+                hash,
+                node,
+            }),
+        }
     }
 
     /// Removes node and all edges for it
     ///
     /// Returns removed node or None, if node not found
     ///
-    /// Computes in **O(e)** (average) where e = node's edges count
+    /// Computes in **O(n)** where n = nodes count
     pub fn remove_node(&mut self, node_index: usize) -> Option<N> {
-        if node_index >= self.nodes.len() || node_index >= self.adjacency.len() {
-            return None;
-        }
+        self.nodes.get_node_by_index(node_index)?;
 
-        for i in 0..self.nodes.len() {
-            if i >= self.adjacency.len() {
-                break;
-            }
-
-            let positions = [(i, node_index), (node_index, i)];
-
-            positions
-                .iter()
-                .for_each(|(from, to)| self.adjacency[*from][*to] = None);
+        for i in 0..self.nodes.bound() {
+            self.remove_edge(i, node_index);
+            self.remove_edge(node_index, i);
         }
 
         self.nodes.remove(node_index)
@@ -100,35 +148,126 @@ where
 
     /// Adds edge between two nodes
     ///
-    /// Computes in **O(1)** (average)
-    /// Worst case **O(n ^ 2)** where n = nodes count
+    /// Computes in **O(1)**
     ///
     /// **Panics** if some of nodes not exists or edge already exists
     pub fn add_edge(&mut self, from: usize, to: usize, weight: T) {
-        let max_idx = cmp::max(from, to);
-        if max_idx >= self.nodes.len() {
-            panic!(
-                "Can't add edge for not existing node with index {}",
-                max_idx
-            );
+        match self.try_add_edge(from, to, weight) {
+            Ok(()) => {}
+            Err(GraphError::NodeNotFound(idx) | GraphError::RemovedNode(idx)) => {
+                panic!("Can't add edge for not existing node with index {}", idx)
+            }
+            Err(err) => panic!("{}", err),
         }
+    }
 
-        if self.update_edge(from, to, weight).is_some() {
-            panic!("Edge from {} to {} already exists", from, to);
+    /// Adds edge between two nodes
+    ///
+    /// Returns error if some of nodes not exists or edge already exists,
+    /// in this case graph stays unchanged
+    ///
+    /// Computes in **O(1)**
+    pub fn try_add_edge(&mut self, from: usize, to: usize, weight: T) -> Result<(), GraphError> {
+        let mut missing = [from, to]
+            .into_iter()
+            .filter_map(|idx| self.nodes.try_get(idx).err());
+
+        if let Some(err) = missing.next_back() {
+            return Err(err);
+        }
+
+        if self.contains_edge(from, to) {
+            return Err(GraphError::DuplicateEdge(from, to));
+        }
+
+        self.update_edge(from, to, weight);
+        Ok(())
+    }
+
+    /// Adds edge between two nodes or replaces weight of existing one
+    ///
+    /// Returns previous weight or None if edge was added
+    ///
+    /// Computes in **O(1)**
+    ///
+    /// **Panics** if some of nodes not exists
+    pub fn upsert_edge(&mut self, from: usize, to: usize, weight: T) -> Option<T> {
+        match self.try_upsert_edge(from, to, weight) {
+            Ok(old) => old,
+            Err(GraphError::NodeNotFound(idx) | GraphError::RemovedNode(idx)) => {
+                panic!("Can't add edge for not existing node with index {}", idx)
+            }
+            Err(err) => panic!("{}", err),
         }
     }
 
+    /// Adds edge between two nodes or replaces weight of existing one
+    ///
+    /// Returns previous weight, None if edge was added
+    /// or error if some of nodes not exists
+    ///
+    /// Computes in **O(1)**
+    pub fn try_upsert_edge(
+        &mut self,
+        from: usize,
+        to: usize,
+        weight: T,
+    ) -> Result<Option<T>, GraphError> {
+        let mut missing = [from, to]
+            .into_iter()
+            .filter_map(|idx| self.nodes.try_get(idx).err());
+
+        if let Some(err) = missing.next_back() {
+            return Err(err);
+        }
+
+        Ok(self.update_edge(from, to, weight))
+    }
+
+    /// Returns mutable reference to edge's weight
+    /// or None if edge not exists
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn get_edge_mut(&mut self, from: usize, to: usize) -> Option<&mut T> {
+        let position = self.position(from, to)?;
+        self.adjacency[position].as_mut()
+    }
+
+    /// Returns guard for changing node in place
+    /// or None if node not exists
+    ///
+    /// Changes should be applied with [`NodeMut::commit`], which re-hashes node,
+    /// so it stays reachable by [`MatrixGraph::get_index_of`].
+    /// If changed node equals to another node, original node is restored
+    /// and error is returned
+    #[inline]
+    pub fn get_node_mut(&mut self, node_index: usize) -> Option<NodeMut<'_, N, S>>
+    where
+        N: Clone,
+    {
+        self.nodes.get_mut(node_index)
+    }
+
+    /// Replaces node keeping it's index and edges
+    ///
+    /// Returns old node or error if node not exists
+    /// or another node is equal to the new one
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn try_replace_node(&mut self, node_index: usize, node: N) -> Result<N, GraphError> {
+        self.nodes.try_replace(node_index, node)
+    }
+
     /// Removes edge between two nodes
     ///
     /// Returns edge's weight if removed else None
     ///
     /// Computes in **O(1)**
     pub fn remove_edge(&mut self, from_node: usize, to_node: usize) -> Option<T> {
-        if cmp::max(from_node, to_node) >= self.nodes.len() {
-            return None;
-        }
-
-        let old_edge = mem::replace(&mut self.adjacency[from_node][to_node], None);
+        let position = self.position(from_node, to_node)?;
+        let old_edge = self.adjacency[position].take();
 
         if old_edge.is_some() {
             self.edge_count -= 1;
@@ -153,9 +292,17 @@ where
         self.edge_count
     }
 
+    /// Returns upper bound of node indices
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn node_bound(&self) -> usize {
+        self.nodes.bound()
+    }
+
     /// Returns iterator over nodes
     #[inline]
-    pub fn node_iter(&mut self) -> NodeIterator<N> {
+    pub fn node_iter(&mut self) -> NodeIterator<'_, N, S> {
         NodeIterator::new(&self.nodes)
     }
 
@@ -172,10 +319,7 @@ where
     /// Computes in **O(1)**
     #[inline]
     pub fn contains_edge(&self, from: usize, to: usize) -> bool {
-        if cmp::max(from, to) >= self.adjacency.len() {
-            return false;
-        }
-        self.adjacency[from][to].is_some()
+        self.get_edge_by_index(from, to).is_some()
     }
 
     /// Returns index of node or None if not found
@@ -186,9 +330,71 @@ where
         self.nodes.get_index_of(node)
     }
 
+    /// Returns count of nodes which graph can hold
+    /// without reallocating adjacency matrix
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Reserves space for at least `additional` more nodes
+    ///
+    /// Computes in **O(c ^ 2)** where c = new capacity, if reallocates
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.nodes.bound() + additional;
+
+        if required > self.capacity {
+            self.resize(cmp::max(required, self.capacity * 2));
+        }
+    }
+
+    /// Shrinks adjacency matrix to fit upper bound of node indices
+    ///
+    /// Removed nodes still take space, use [`MatrixGraph::compact`] to free it
+    ///
+    /// Computes in **O(b ^ 2)** where b = nodes count including removed
+    pub fn shrink_to_fit(&mut self) {
+        self.resize(self.nodes.bound());
+        self.adjacency.shrink_to_fit();
+    }
+
+    /// Moves all nodes to the front keeping their order
+    /// and shrinks adjacency matrix to fit nodes count
+    ///
+    /// Returns map from old indices to new ones,
+    /// where removed nodes are mapped to None.
+    /// Handles of moved nodes become stale
+    ///
+    /// Computes in **O(b ^ 2)** where b = nodes count including removed
+    pub fn compact(&mut self) -> Vec<Option<usize>> {
+        let map = self.nodes.compact();
+        let count = self.nodes.bound();
+        let old_capacity = mem::replace(&mut self.capacity, count);
+        let old = mem::replace(&mut self.adjacency, Self::empty_matrix(count));
+
+        for (position, weight) in old.into_iter().enumerate() {
+            let (from, to) = (position / old_capacity, position % old_capacity);
+
+            if let (Some(weight), Some(Some(new_from)), Some(Some(new_to))) =
+                (weight, map.get(from), map.get(to))
+            {
+                self.adjacency[new_from * count + new_to] = Some(weight);
+            }
+        }
+
+        map
+    }
+
+    /// Splits graph into node storage, adjacency matrix and it's row stride
+    pub(crate) fn into_parts(self) -> (NodeStorage<N, S>, Vec<Option<T>>, usize) {
+        (self.nodes, self.adjacency, self.capacity)
+    }
+
     fn update_edge(&mut self, from: usize, to: usize, weight: T) -> Option<T> {
-        self.extend_capacity_if_needed(from, to);
-        let last_edge = mem::replace(&mut self.adjacency[from][to], Some(weight));
+        let position = self.position(from, to)?;
+        let last_edge = self.adjacency[position].replace(weight);
 
         if last_edge.is_none() {
             self.edge_count += 1;
@@ -197,29 +403,212 @@ where
         last_edge
     }
 
-    fn extend_capacity_if_needed(&mut self, from: usize, to: usize) {
-        let p = cmp::max(from, to);
+    /// Position of edge in adjacency matrix
+    #[inline]
+    fn position(&self, from: usize, to: usize) -> Option<usize> {
+        if cmp::max(from, to) >= self.capacity {
+            return None;
+        }
+        Some(from * self.capacity + to)
+    }
 
-        if p < self.adjacency.len() {
+    /// Reallocates adjacency matrix to hold `new_capacity` nodes
+    /// dropping edges of nodes which don't fit
+    fn resize(&mut self, new_capacity: usize) {
+        if new_capacity == self.capacity {
             return;
         }
 
-        let new_capacity = cmp::max(4, p + 1).next_power_of_two().pow(2);
-        let diff = new_capacity - self.adjacency.len();
-        self.adjacency
-            .extend((0..diff).map(|_| (0..new_capacity).map(|_| None).collect()));
+        let old_capacity = mem::replace(&mut self.capacity, new_capacity);
+        let mut old = mem::replace(&mut self.adjacency, Self::empty_matrix(new_capacity));
+        let keep = cmp::min(old_capacity, new_capacity);
+
+        for from in 0..keep {
+            let old_row = &mut old[from * old_capacity..from * old_capacity + keep];
+            let new_row = &mut self.adjacency[from * new_capacity..from * new_capacity + keep];
+
+            for (old_cell, new_cell) in old_row.iter_mut().zip(new_row.iter_mut()) {
+                *new_cell = old_cell.take();
+            }
+        }
+    }
 
-        for i in 0..diff {
-            self.adjacency[i].extend((0..diff).map(|_| None));
+    /// Grows adjacency matrix if node with index `idx` doesn't fit
+    fn grow_for(&mut self, idx: usize) {
+        if idx >= self.capacity {
+            let new_capacity = cmp::max(cmp::max(4, idx + 1), self.capacity * 2);
+            self.resize(new_capacity);
         }
     }
+
+    fn empty_matrix(capacity: usize) -> Vec<Option<T>> {
+        let mut adjacency = Vec::with_capacity(capacity * capacity);
+        adjacency.resize_with(capacity * capacity, || None);
+        adjacency
+    }
+}
+
+impl<N, T, S> MatrixGraph<N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    /// Returns neighbors of node or error if node not exists
+    pub fn try_neighbors(
+        &self,
+        node: usize,
+    ) -> Result<MatrixGraphNeighborsIterator<'_, N, T, S>, GraphError> {
+        self.nodes.try_get(node)?;
+
+        let start = node * self.capacity;
+        let row = &self.adjacency[start..start + self.nodes.bound()];
+        Ok(MatrixGraphNeighborsIterator::new(&self.nodes, row))
+    }
+
+    /// Returns predecessors of node or error if node not exists
+    pub fn try_predecessors(
+        &self,
+        node: usize,
+    ) -> Result<MatrixGraphPredecessorsIterator<'_, N, T, S>, GraphError> {
+        self.nodes.try_get(node)?;
+
+        Ok(MatrixGraphPredecessorsIterator {
+            nodes: &self.nodes,
+            edges: self.edges_to(node),
+        })
+    }
+
+    /// Returns iterator over all edges ordered by source and target
+    ///
+    /// Computes in **O(c ^ 2)** where c = capacity
+    #[inline]
+    pub fn edges(&self) -> MatrixGraphEdgesIterator<'_, T> {
+        MatrixGraphEdgesIterator::all(&self.adjacency, self.capacity)
+    }
+
+    /// Returns iterator over outgoing edges of node ordered by target,
+    /// which is empty if node not exists
+    ///
+    /// Computes in **O(c)** where c = capacity
+    #[inline]
+    pub fn edges_from(&self, node: usize) -> MatrixGraphEdgesIterator<'_, T> {
+        MatrixGraphEdgesIterator::row(&self.adjacency, self.capacity, node)
+    }
+
+    /// Returns iterator over incoming edges of node ordered by source,
+    /// which is empty if node not exists
+    ///
+    /// Computes in **O(c)** where c = capacity
+    #[inline]
+    pub fn edges_to(&self, node: usize) -> MatrixGraphEdgesIterator<'_, T> {
+        MatrixGraphEdgesIterator::column(&self.adjacency, self.capacity, node)
+    }
+
+    /// Returns count of incoming edges of node
+    ///
+    /// Computes in **O(c)** where c = capacity
+    #[inline]
+    pub fn in_degree(&self, node: usize) -> usize {
+        self.edges_to(node).count()
+    }
+
+    /// Returns count of outgoing edges of node
+    ///
+    /// Computes in **O(c)** where c = capacity
+    #[inline]
+    pub fn out_degree(&self, node: usize) -> usize {
+        self.edges_from(node).count()
+    }
+
+    /// Returns generational handle of existing node
+    /// or None if node not found
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn handle(&self, node_index: usize) -> Option<NodeIndex> {
+        self.nodes.handle(node_index)
+    }
+
+    /// Returns index of node behind handle
+    /// or None if node was removed after handle creation
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn resolve(&self, handle: NodeIndex) -> Option<usize> {
+        self.nodes.resolve(handle)
+    }
+
+    /// Returns node by handle or None if handle is stale
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn get_node_by_handle(&self, handle: NodeIndex) -> Option<&N> {
+        self.get_node_by_index(self.resolve(handle)?)
+    }
+
+    /// Removes node by handle and all edges for it
+    ///
+    /// Returns removed node or error if handle is stale
+    ///
+    /// Computes in **O(n)** where n = nodes count
+    pub fn remove_node_by_handle(&mut self, handle: NodeIndex) -> Result<N, GraphError> {
+        let node_index = self.try_resolve(handle)?;
+        self.remove_node(node_index)
+            .ok_or(GraphError::RemovedNode(node_index))
+    }
+
+    /// Adds edge between two nodes given by handles
+    ///
+    /// Returns error if some of handles is stale or edge already exists,
+    /// in this case graph stays unchanged
+    ///
+    /// Computes in **O(1)**
+    pub fn add_edge_by_handle(
+        &mut self,
+        from: NodeIndex,
+        to: NodeIndex,
+        weight: T,
+    ) -> Result<(), GraphError> {
+        let (from, to) = (self.try_resolve(from)?, self.try_resolve(to)?);
+        self.try_add_edge(from, to, weight)
+    }
+
+    /// Removes edge between two nodes given by handles
+    ///
+    /// Returns edge's weight if removed, None if edge not exists
+    /// or error if some of handles is stale
+    ///
+    /// Computes in **O(1)**
+    pub fn remove_edge_by_handle(
+        &mut self,
+        from: NodeIndex,
+        to: NodeIndex,
+    ) -> Result<Option<T>, GraphError> {
+        let (from, to) = (self.try_resolve(from)?, self.try_resolve(to)?);
+        Ok(self.remove_edge(from, to))
+    }
+
+    /// Returns neighbors of node given by handle
+    /// or error if handle is stale
+    pub fn neighbors_by_handle(
+        &self,
+        handle: NodeIndex,
+    ) -> Result<MatrixGraphNeighborsIterator<'_, N, T, S>, GraphError> {
+        self.try_neighbors(self.try_resolve(handle)?)
+    }
+
+    fn try_resolve(&self, handle: NodeIndex) -> Result<usize, GraphError> {
+        self.resolve(handle)
+            .ok_or(GraphError::RemovedNode(handle.index()))
+    }
 }
 
 /////////////////////////////////////////////////////////////////////////////////////
 
-impl<N, T> GetNodeByIndex<N> for MatrixGraph<N, T>
+impl<N, T, S> GetNodeByIndex<N> for MatrixGraph<N, T, S>
 where
     N: MatrixGraphNode,
+    S: BuildHasher,
 {
     #[inline]
     fn get_node_by_index(&self, node_idx: usize) -> Option<&N> {
@@ -227,47 +616,141 @@ where
     }
 }
 
-impl<N, T> GetEdgeByIndex<T> for MatrixGraph<N, T>
+impl<N, T, S> NodeBound for MatrixGraph<N, T, S>
 where
     N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    #[inline]
+    fn node_bound(&self) -> usize {
+        self.nodes.bound()
+    }
+}
+
+impl<N, T, S> GetEdgeByIndex<T> for MatrixGraph<N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
 {
     #[inline]
     fn get_edge_by_index(&self, from: usize, to: usize) -> Option<&T> {
-        if cmp::max(from, to) >= self.adjacency.len() {
-            return None;
+        self.adjacency[self.position(from, to)?].as_ref()
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////////////
+
+/// Entry of node in graph, which is either existing or vacant
+///
+/// Created by [`MatrixGraph::node_entry`]
+pub enum Entry<'a, N, T, S = DefaultBuildHasher>
+where
+    N: MatrixGraphNode,
+{
+    Occupied(usize),
+    Vacant(VacantEntry<'a, N, T, S>),
+}
+
+/// Entry of node which is not in graph yet
+pub struct VacantEntry<'a, N, T, S = DefaultBuildHasher>
+where
+    N: MatrixGraphNode,
+{
+    graph: &'a mut MatrixGraph<N, T, S>,
+    hash: u64,
+    node: N,
+}
+
+impl<'a, N, T, S> Entry<'a, N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    /// Returns index of node or None if node is not in graph
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            Entry::Occupied(index) => Some(*index),
+            Entry::Vacant(_) => None,
         }
-        self.adjacency[from][to].as_ref()
+    }
+
+    /// Returns index of existing node or inserts it
+    ///
+    /// Computes in **O(1)** (average amortized)
+    pub fn or_insert(self) -> usize {
+        self.or_insert_with(|_| {})
+    }
+
+    /// Returns index of existing node or inserts it
+    /// and calls `f` with index of inserted node
+    ///
+    /// Computes in **O(1)** (average amortized)
+    pub fn or_insert_with<F>(self, f: F) -> usize
+    where
+        F: FnOnce(usize),
+    {
+        match self {
+            Entry::Occupied(index) => index,
+            Entry::Vacant(entry) => {
+                let index = entry.insert();
+                f(index);
+                index
+            }
+        }
+    }
+}
+
+impl<'a, N, T, S> VacantEntry<'a, N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    /// Returns node which will be inserted
+    #[inline]
+    pub fn node(&self) -> &N {
+        &self.node
+    }
+
+    /// Inserts node in graph
+    ///
+    /// Returns index of new node
+    pub fn insert(self) -> usize {
+        let idx = self.graph.nodes.insert_hashed(self.hash, self.node);
+        self.graph.grow_for(idx);
+        idx
     }
 }
 
 /////////////////////////////////////////////////////////////////////////////////////
 
-pub struct NodeIterator<'a, N>
+pub struct NodeIterator<'a, N, S = DefaultBuildHasher>
 where
     N: MatrixGraphNode,
 {
-    nodes: &'a NodeStorage<N>,
+    nodes: &'a NodeStorage<N, S>,
     index: usize,
 }
 
-impl<'a, N> NodeIterator<'a, N>
+impl<'a, N, S> NodeIterator<'a, N, S>
 where
     N: MatrixGraphNode,
 {
-    pub fn new(nodes: &'a NodeStorage<N>) -> Self {
+    pub fn new(nodes: &'a NodeStorage<N, S>) -> Self {
         Self { nodes, index: 0 }
     }
 }
 
-impl<'a, N> Iterator for NodeIterator<'a, N>
+impl<'a, N, S> Iterator for NodeIterator<'a, N, S>
 where
     N: MatrixGraphNode,
+    S: BuildHasher,
 {
     type Item = &'a N;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            if self.index >= self.nodes.len() {
+            if self.index >= self.nodes.bound() {
                 return None;
             }
             self.index += 1;
@@ -281,18 +764,99 @@ where
 
 ////////////////////////////////////////////////////////////////////////////////
 
-pub struct MatrixGraphNeighborsIterator<'a, N, T>
+/// Iterates over edges stored in adjacency matrix
+/// visiting cells in given positions
+pub struct MatrixGraphEdgesIterator<'a, T> {
+    adjacency: &'a [Option<T>],
+    stride: usize,
+    positions: StepBy<Range<usize>>,
+}
+
+impl<'a, T> MatrixGraphEdgesIterator<'a, T> {
+    /// Iterates over whole matrix
+    pub fn all(adjacency: &'a [Option<T>], stride: usize) -> Self {
+        Self {
+            adjacency,
+            stride,
+            positions: (0..adjacency.len()).step_by(1),
+        }
+    }
+
+    /// Iterates over row of matrix, so over outgoing edges of `from`
+    pub fn row(adjacency: &'a [Option<T>], stride: usize, from: usize) -> Self {
+        let positions = match from < stride {
+            true => from * stride..(from + 1) * stride,
+            false => 0..0,
+        };
+
+        Self {
+            adjacency,
+            stride,
+            positions: positions.step_by(1),
+        }
+    }
+
+    /// Iterates over column of matrix, so over incoming edges of `to`
+    pub fn column(adjacency: &'a [Option<T>], stride: usize, to: usize) -> Self {
+        let positions = match to < stride {
+            true => to..stride * stride,
+            false => 0..0,
+        };
+
+        Self {
+            adjacency,
+            stride,
+            positions: positions.step_by(cmp::max(stride, 1)),
+        }
+    }
+}
+
+impl<'a, T> Iterator for MatrixGraphEdgesIterator<'a, T> {
+    type Item = EdgeRef<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let position = self.positions.next()?;
+
+            if let Some(weight) = &self.adjacency[position] {
+                return Some(EdgeRef {
+                    from: position / self.stride,
+                    to: position % self.stride,
+                    weight,
+                });
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+pub struct MatrixGraphNeighborsIterator<'a, N, T, S = DefaultBuildHasher>
 where
     N: MatrixGraphNode,
 {
     column: usize,
-    nodes: &'a NodeStorage<N>,
-    adjacency: &'a Vec<Option<T>>,
+    nodes: &'a NodeStorage<N, S>,
+    adjacency: &'a [Option<T>],
 }
 
-impl<'a, N, T> Iterator for MatrixGraphNeighborsIterator<'a, N, T>
+impl<'a, N, T, S> MatrixGraphNeighborsIterator<'a, N, T, S>
 where
     N: MatrixGraphNode,
+{
+    pub fn new(nodes: &'a NodeStorage<N, S>, adjacency: &'a [Option<T>]) -> Self {
+        Self {
+            column: 0,
+            nodes,
+            adjacency,
+        }
+    }
+}
+
+impl<'a, N, T, S> Iterator for MatrixGraphNeighborsIterator<'a, N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
 {
     type Item = (usize, &'a N);
 
@@ -314,60 +878,124 @@ where
     }
 }
 
-impl<'a, N: 'a, T> Neighbors<'a, N, MatrixGraphNeighborsIterator<'a, N, T>> for MatrixGraph<N, T>
+/// Iterates over nodes which have edge to given node
+/// walking column of adjacency matrix
+pub struct MatrixGraphPredecessorsIterator<'a, N, T, S = DefaultBuildHasher>
 where
     N: MatrixGraphNode,
 {
-    fn neighbors(
-        &'a self,
-        node: usize,
-    ) -> IteratorHandle<'a, N, MatrixGraphNeighborsIterator<'a, N, T>> {
-        if node >= self.nodes.len() {
-            panic!("Node with index {} not found", node);
-        }
+    nodes: &'a NodeStorage<N, S>,
+    edges: MatrixGraphEdgesIterator<'a, T>,
+}
 
-        let iterator = MatrixGraphNeighborsIterator {
-            column: 0,
-            nodes: &self.nodes,
-            adjacency: &self.adjacency[node],
-        };
+impl<'a, N, T, S> Iterator for MatrixGraphPredecessorsIterator<'a, N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    type Item = (usize, &'a N);
 
-        IteratorHandle { iterator }
+    fn next(&mut self) -> Option<Self::Item> {
+        let edge = self.edges.next()?;
+        Some((edge.from, self.nodes.get_node_by_index(edge.from)?))
     }
 }
 
-impl<N, T> Adjacency<N, T> for MatrixGraph<N, T>
+////////////////////////////////////////////////////////////////////////////////
+
+impl<N, T, S> Graph for MatrixGraph<N, T, S>
 where
-    N: MatrixGraphNode + Clone,
+    N: MatrixGraphNode,
+    S: BuildHasher,
 {
-    fn get_adjacency_matrix(&self) -> AdjacencyMatrix<N, T> {
-        AdjacencyMatrix {
-            nodes: &self.nodes,
-            edges: &self.adjacency,
+    type Node = N;
+    type Weight = T;
+    type Neighbors<'a>
+        = MatrixGraphNeighborsIterator<'a, N, T, S>
+    where
+        Self: 'a;
+    type Edges<'a>
+        = MatrixGraphEdgesIterator<'a, T>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        match self.try_neighbors(node) {
+            Ok(neighbors) => neighbors,
+            Err(_) => panic!("Node with index {} not found", node),
         }
     }
+
+    #[inline]
+    fn edges_from(&self, node: usize) -> Self::Edges<'_> {
+        MatrixGraph::edges_from(self, node)
+    }
+}
+
+impl<N, T, S> IncomingNeighbors for MatrixGraph<N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    type Predecessors<'a>
+        = MatrixGraphPredecessorsIterator<'a, N, T, S>
+    where
+        Self: 'a;
+    type IncomingEdges<'a>
+        = MatrixGraphEdgesIterator<'a, T>
+    where
+        Self: 'a;
+
+    fn predecessors(&self, node: usize) -> Self::Predecessors<'_> {
+        match self.try_predecessors(node) {
+            Ok(predecessors) => predecessors,
+            Err(_) => panic!("Node with index {} not found", node),
+        }
+    }
+
+    #[inline]
+    fn edges_to(&self, node: usize) -> Self::IncomingEdges<'_> {
+        MatrixGraph::edges_to(self, node)
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 
-impl<'a, N: 'a, T> BfsIterable<'a, N, MatrixGraphNeighborsIterator<'a, N, T>, T, MatrixGraph<N, T>>
-    for MatrixGraph<N, T>
+impl<N, T, S> Adjacency<N, T, S> for MatrixGraph<N, T, S>
 where
-    N: MatrixGraphNode,
+    N: MatrixGraphNode + Clone,
+    S: BuildHasher,
 {
-    fn get_graph(&'a self) -> &'a MatrixGraph<N, T> {
-        self
+    fn get_adjacency_matrix(&self) -> AdjacencyMatrix<'_, N, T, S> {
+        AdjacencyMatrix {
+            nodes: &self.nodes,
+            edges: &self.adjacency,
+            stride: self.capacity,
+        }
     }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 
-impl<N, T> fmt::Display for MatrixGraph<N, T>
+/// Prints every node with it's neighbors in breadth first order,
+/// including nodes of disconnected components
+impl<N, T, S> fmt::Display for MatrixGraph<N, T, S>
 where
     N: MatrixGraphNode + fmt::Display,
+    S: BuildHasher,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for entry in self.bfs_iter(0) {
+        for entry in self.bfs_iter_all() {
             let neighs = if entry.edges.is_empty() {
                 "[ ]".to_string()
             } else {
@@ -418,7 +1046,7 @@ mod tests {
             (3, 1, 4),
         ];
 
-        let g = MatrixGraph::<u32, u8>::from_edges(edges.into_iter());
+        let g = MatrixGraph::<u32, u8>::from_edges(edges);
         assert_eq!(g.node_count(), 5, "Nodes: {}", g.node_count());
         assert_eq!(g.edge_count(), edges.len(), "Nodes: {}", g.edge_count());
 
@@ -605,6 +1233,428 @@ mod tests {
         g.neighbors(6);
     }
 
+    #[test]
+    fn test_resolves_handle_of_existing_node() {
+        let mut g = create_graph();
+        let a_idx = g.add_node(1);
+        let b_idx = g.add_node(2);
+        let a = g.handle(a_idx).unwrap();
+        let b = g.handle(b_idx).unwrap();
+
+        assert_eq!(g.get_node_by_handle(a), Some(&1));
+        assert_eq!(g.add_edge_by_handle(a, b, ()), Ok(()));
+        assert!(g.contains_edge(a_idx, b_idx));
+        assert_eq!(
+            g.add_edge_by_handle(a, b, ()),
+            Err(GraphError::DuplicateEdge(a_idx, b_idx))
+        );
+        assert_eq!(g.neighbors_by_handle(a).unwrap().count(), 1);
+        assert_eq!(g.remove_edge_by_handle(a, b), Ok(Some(())));
+        assert_eq!(g.remove_edge_by_handle(a, b), Ok(None));
+        assert_eq!(g.remove_node_by_handle(b), Ok(2));
+    }
+
+    #[test]
+    fn test_stale_handle_not_resolves_to_new_node() {
+        let mut g = create_graph();
+        let a_idx = g.add_node(1);
+        let b_idx = g.add_node(2);
+        let stale = g.handle(b_idx).unwrap();
+        let a = g.handle(a_idx).unwrap();
+
+        g.remove_node(b_idx);
+        assert_eq!(g.add_node(3), b_idx);
+
+        assert!(g.resolve(stale).is_none());
+        assert!(g.get_node_by_handle(stale).is_none());
+        let removed = GraphError::RemovedNode(b_idx);
+        assert_eq!(g.add_edge_by_handle(a, stale, ()), Err(removed));
+        assert!(g.neighbors_by_handle(stale).is_err());
+        assert_eq!(g.remove_edge_by_handle(stale, a), Err(removed));
+        assert_eq!(g.remove_node_by_handle(stale), Err(removed));
+        assert_eq!(g.edge_count(), 0);
+        assert_eq!(g.node_count(), 2);
+    }
+
+    /// Hasher which uses integer nodes as their hashes
+    #[derive(Default)]
+    struct IntHasher(u64);
+
+    impl std::hash::Hasher for IntHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.0 = self.0.rotate_left(8) ^ u64::from(*byte);
+            }
+        }
+
+        fn write_u32(&mut self, i: u32) {
+            self.0 = u64::from(i);
+        }
+    }
+
+    #[test]
+    fn test_uses_custom_hasher() {
+        let edges = [(1, 2, ()), (2, 3, ()), (3, 1, ())];
+        let g = MatrixGraph::<u32, (), std::hash::BuildHasherDefault<IntHasher>>::from_edges(edges);
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.get_index_of(&3), Some(2));
+        assert!(g.contains_edge(2, 0));
+    }
+
+    #[test]
+    fn test_creates_with_hasher() {
+        let mut g = MatrixGraph::<u32, ()>::with_hasher(Default::default());
+        let idx = g.add_node(7);
+        assert_eq!(g.get_index_of(&7), Some(idx));
+    }
+
+    #[test]
+    fn test_compact_remaps_nodes_and_edges() {
+        let mut g =
+            MatrixGraph::<u32, u32>::from_edges([(1, 2, 12), (2, 3, 23), (3, 4, 34), (4, 1, 41)]);
+        for node in 5..20 {
+            g.add_node(node);
+        }
+        for idx in 4..19 {
+            g.remove_node(idx);
+        }
+        g.remove_node(1);
+
+        let map = g.compact();
+
+        assert_eq!(map.len(), 19);
+        assert_eq!(map[..5], [Some(0), None, Some(1), Some(2), None]);
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.node_bound(), 3);
+        assert_eq!(g.capacity(), 3);
+        assert_eq!(g.adjacency.len(), 9);
+        assert_eq!(g.edge_count(), 2);
+        assert_eq!(g.get_index_of(&4), Some(2));
+        assert_eq!(g.get_edge_by_index(1, 2), Some(&34));
+        assert_eq!(g.get_edge_by_index(2, 0), Some(&41));
+
+        let idx = g.add_node(21);
+        g.add_edge(idx, 0, 0);
+        assert_eq!(g.edge_count(), 3);
+    }
+
+    #[test]
+    fn test_grows_capacity_by_doubling() {
+        let mut g = create_graph();
+        for node in 0..17 {
+            g.add_node(node);
+        }
+        assert_eq!(g.capacity(), 32);
+        assert_eq!(g.adjacency.len(), 32 * 32);
+    }
+
+    #[test]
+    fn test_keeps_edges_after_growing() {
+        let mut g = MatrixGraph::<u32, u32>::default();
+        let a = g.add_node(0);
+        let b = g.add_node(1);
+        g.add_edge(b, a, 10);
+        for node in 2..10 {
+            let idx = g.add_node(node);
+            g.add_edge(idx, a, node);
+        }
+
+        assert_eq!(g.get_edge_by_index(b, a), Some(&10));
+        assert_eq!(g.get_edge_by_index(9, a), Some(&9));
+        assert_eq!(g.edge_count(), 9);
+    }
+
+    #[test]
+    fn test_creates_with_capacity() {
+        let mut g = MatrixGraph::<u32, ()>::with_capacity(10);
+        assert_eq!(g.capacity(), 10);
+        for node in 0..10 {
+            g.add_node(node);
+        }
+        assert_eq!(g.capacity(), 10);
+    }
+
+    #[test]
+    fn test_reserves_capacity() {
+        let mut g = create_graph();
+        g.add_node(1);
+        g.reserve(20);
+        assert!(g.capacity() >= 21);
+    }
+
+    #[test]
+    fn test_shrinks_to_fit() {
+        let mut g = MatrixGraph::<u32, u32>::with_capacity(100);
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        g.add_edge(a, b, 3);
+
+        g.shrink_to_fit();
+
+        assert_eq!(g.capacity(), 2);
+        assert_eq!(g.adjacency.len(), 4);
+        assert_eq!(g.get_edge_by_index(a, b), Some(&3));
+        assert_eq!(g.neighbors(a).count(), 1);
+    }
+
+    #[test]
+    fn test_try_add_node_returns_error_on_existing_node() {
+        let mut g = create_graph();
+        assert_eq!(g.try_add_node(34), Ok(0));
+        assert_eq!(g.try_add_node(34), Err(GraphError::DuplicateNode));
+        assert_eq!(g.node_count(), 1);
+    }
+
+    #[test]
+    fn test_try_add_edge_returns_errors() {
+        let mut g = MatrixGraph::<u32, u32>::default();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        g.remove_node(b);
+
+        assert_eq!(g.try_add_edge(a, 5, 1), Err(GraphError::NodeNotFound(5)));
+        assert_eq!(g.try_add_edge(b, a, 1), Err(GraphError::RemovedNode(b)));
+        assert_eq!(g.try_add_edge(a, a, 1), Ok(()));
+        assert_eq!(
+            g.try_add_edge(a, a, 2),
+            Err(GraphError::DuplicateEdge(a, a))
+        );
+        assert_eq!(g.get_edge_by_index(a, a), Some(&1));
+        assert_eq!(g.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_try_neighbors_returns_error_on_missing_node() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        g.add_edge(a, b, ());
+        g.remove_node(b);
+
+        assert_eq!(g.try_neighbors(a).unwrap().count(), 0);
+        assert!(matches!(
+            g.try_neighbors(b),
+            Err(GraphError::RemovedNode(1))
+        ));
+        assert!(matches!(
+            g.try_neighbors(6),
+            Err(GraphError::NodeNotFound(6))
+        ));
+    }
+
+    #[test]
+    fn test_node_entry_returns_existing_index() {
+        let mut g = create_graph();
+        let a = g.add_node(7);
+
+        let entry = g.node_entry(7);
+        assert_eq!(entry.index(), Some(a));
+        assert_eq!(entry.or_insert(), a);
+        assert_eq!(g.node_count(), 1);
+    }
+
+    #[test]
+    fn test_node_entry_inserts_missing_node() {
+        let mut g = create_graph();
+        g.add_node(7);
+
+        let entry = g.node_entry(9);
+        assert_eq!(entry.index(), None);
+
+        let mut inserted = None;
+        let idx = entry.or_insert_with(|idx| inserted = Some(idx));
+        assert_eq!(inserted, Some(idx));
+        assert_eq!(g.get_index_of(&9), Some(idx));
+        assert_eq!(g.node_count(), 2);
+
+        let mut called = false;
+        assert_eq!(g.node_entry(9).or_insert_with(|_| called = true), idx);
+        assert!(!called);
+    }
+
+    #[test]
+    fn test_node_entry_grows_matrix_and_reuses_removed_slot() {
+        let mut g = create_graph();
+        for i in 0..10 {
+            g.node_entry(i).or_insert();
+        }
+        assert!(g.capacity() >= 10);
+
+        g.remove_node(3);
+        let idx = g.node_entry(42).or_insert();
+        assert_eq!(idx, 3);
+        g.add_edge(idx, 9, ());
+        assert!(g.contains_edge(3, 9));
+    }
+
+    #[test]
+    fn test_changes_edge_weight_in_place() {
+        let mut g = MatrixGraph::<u32, u32>::from_edges([(1, 2, 3)]);
+
+        *g.get_edge_mut(0, 1).unwrap() += 4;
+        assert_eq!(g.get_edge_by_index(0, 1), Some(&7));
+        assert!(g.get_edge_mut(1, 0).is_none());
+        assert!(g.get_edge_mut(10, 0).is_none());
+    }
+
+    #[test]
+    fn test_upserts_edge() {
+        let mut g = MatrixGraph::<u32, u32>::from_edges([(1, 2, 3)]);
+
+        assert_eq!(g.upsert_edge(0, 1, 5), Some(3));
+        assert_eq!(g.upsert_edge(1, 0, 6), None);
+        assert_eq!(g.get_edge_by_index(0, 1), Some(&5));
+        assert_eq!(g.get_edge_by_index(1, 0), Some(&6));
+        assert_eq!(g.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_try_upsert_edge_returns_error_on_missing_node() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        g.remove_node(b);
+
+        assert_eq!(
+            g.try_upsert_edge(a, 4, ()),
+            Err(GraphError::NodeNotFound(4))
+        );
+        assert_eq!(g.try_upsert_edge(b, a, ()), Err(GraphError::RemovedNode(b)));
+        assert_eq!(g.try_upsert_edge(a, a, ()), Ok(None));
+        assert_eq!(g.try_upsert_edge(a, a, ()), Ok(Some(())));
+        assert_eq!(g.edge_count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't add edge for not existing node with index 4")]
+    fn test_panics_on_upsert_edge_for_not_existing_node() {
+        let mut g = create_graph();
+        g.add_node(1);
+        g.upsert_edge(0, 4, ());
+    }
+
+    #[test]
+    fn test_changes_node_in_place() {
+        let mut g = MatrixGraph::<u32, ()>::from_edges([(1, 2, ())]);
+
+        let mut node = g.get_node_mut(1).unwrap();
+        *node = 5;
+        assert_eq!(node.commit(), Ok(()));
+        assert_eq!(g.get_index_of(&5), Some(1));
+        assert!(!g.contains_node(&2));
+        assert!(g.contains_edge(0, 1));
+
+        assert_eq!(g.try_replace_node(1, 1), Err(GraphError::DuplicateNode));
+        assert_eq!(g.try_replace_node(1, 6), Ok(5));
+        assert_eq!(g.get_index_of(&6), Some(1));
+        assert!(g.contains_edge(0, 1));
+    }
+
+    #[test]
+    fn test_iterates_over_edges_with_weights() {
+        let mut g =
+            MatrixGraph::<u32, u8>::from_edges([(1, 2, 3), (2, 3, 4), (3, 1, 5), (1, 3, 6)]);
+        g.remove_edge(1, 2);
+
+        let actual = g
+            .edges()
+            .map(|edge| (edge.from, edge.to, *edge.weight))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![(0, 1, 3), (0, 2, 6), (2, 0, 5)]);
+    }
+
+    #[test]
+    fn test_iterates_over_edges_from_and_to_node() {
+        let g = MatrixGraph::<u32, u8>::from_edges([(1, 2, 3), (2, 3, 4), (3, 1, 5), (1, 3, 6)]);
+
+        let from = g.edges_from(0).collect::<Vec<_>>();
+        assert_eq!(
+            from,
+            vec![
+                EdgeRef {
+                    from: 0,
+                    to: 1,
+                    weight: &3
+                },
+                EdgeRef {
+                    from: 0,
+                    to: 2,
+                    weight: &6
+                },
+            ]
+        );
+
+        let to = g.edges_to(2).map(|edge| edge.from).collect::<Vec<_>>();
+        assert_eq!(to, vec![0, 1]);
+        assert_eq!(g.edges_from(10).count(), 0);
+        assert_eq!(g.edges_to(10).count(), 0);
+    }
+
+    #[test]
+    fn test_predecessors_walk_column() {
+        let mut g = MatrixGraph::<u32, ()>::from_edges([(1, 2, ()), (3, 2, ()), (2, 4, ())]);
+
+        let actual = g.predecessors(1).map(|(_, node)| *node).collect::<Vec<_>>();
+        assert_eq!(actual, vec![1, 3]);
+        assert_eq!(g.predecessors(0).count(), 0);
+
+        g.remove_node(2);
+        assert_eq!(g.predecessors(1).count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Node with index 6 not found")]
+    fn test_panics_on_getting_predecessors_for_not_existed_node() {
+        let g = create_graph();
+        g.predecessors(6);
+    }
+
+    #[test]
+    fn test_try_predecessors_reports_removed_node() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        g.remove_node(a);
+
+        assert!(matches!(
+            g.try_predecessors(a),
+            Err(GraphError::RemovedNode(0))
+        ));
+    }
+
+    #[test]
+    fn test_counts_degrees() {
+        let g =
+            MatrixGraph::<u32, ()>::from_edges([(1, 2, ()), (1, 3, ()), (3, 2, ()), (2, 2, ())]);
+
+        assert_eq!(g.out_degree(0), 2);
+        assert_eq!(g.in_degree(0), 0);
+        assert_eq!(g.out_degree(1), 1);
+        assert_eq!(g.in_degree(1), 3);
+        assert_eq!(g.in_degree(5), 0);
+    }
+
+    #[test]
+    fn test_empty_graph_has_no_edges() {
+        let g = create_graph();
+        assert_eq!(g.edges().count(), 0);
+        assert_eq!(g.edges_to(0).count(), 0);
+    }
+
+    #[test]
+    fn test_displays_all_components() {
+        let mut g = MatrixGraph::<u32, ()>::from_edges([(1, 2, ()), (3, 4, ())]);
+        g.remove_node(0);
+
+        let expected = "Id: 2, neighbors: [ ], Value: 2\n\
+                        Id: 3, neighbors: [ 4 ], Value: 3\n\
+                        Id: 4, neighbors: [ ], Value: 4\n";
+        assert_eq!(g.to_string(), expected);
+    }
+
     fn create_closure() -> fn(u32) {
         |x| println!("This is x: {}", x)
     }
@@ -616,6 +1666,6 @@ mod tests {
             (2, 3, create_closure()),
             (3, 4, create_closure()),
         ];
-        MatrixGraph::<u32, fn(u32)>::from_edges(edges.into_iter());
+        MatrixGraph::<u32, fn(u32)>::from_edges(edges);
     }
 }
@@ -0,0 +1,624 @@
+use crate::matrix_graph::NodeIterator;
+use crate::node_storage::NodeStorage;
+use crate::types::{EdgeRef, GetEdgeByIndex, GetNodeByIndex, Graph, MatrixGraphNode, NodeBound};
+use std::cmp;
+use std::iter::Flatten;
+use std::slice;
+
+/// Stable identifier of edge in multigraph
+///
+/// Slots of removed edges are reused, but id carries generation of slot,
+/// so id of removed edge will never point to another edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeId {
+    index: usize,
+    generation: usize,
+}
+
+impl EdgeId {
+    /// Returns index of edge slot
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+struct MultiEdge<T> {
+    from: usize,
+    to: usize,
+    weight: T,
+}
+
+/// Place of edge, generation is increased on edge removal
+struct EdgeSlot<T> {
+    generation: usize,
+    edge: Option<MultiEdge<T>>,
+}
+
+/// Graph representation with adjacency matrix
+/// which allows parallel edges between two nodes
+///
+/// Each matrix cell holds ids of all edges between two nodes
+///
+/// Note: it's better to use for dense graph
+pub struct MultiMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    nodes: NodeStorage<N>,
+    adjacency: Vec<Vec<Vec<EdgeId>>>,
+    edges: Vec<EdgeSlot<T>>,
+    free_edges: Vec<usize>,
+    edge_count: usize,
+}
+
+impl<N, T> Default for MultiMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    fn default() -> Self {
+        Self {
+            nodes: NodeStorage::default(),
+            adjacency: Vec::new(),
+            edges: Vec::new(),
+            free_edges: Vec::new(),
+            edge_count: 0,
+        }
+    }
+}
+
+impl<N, T> MultiMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    /// Create MultiMatrixGraph from iterator of tuples
+    /// where each element representes edge between
+    /// two nodes and it's weight
+    ///
+    /// Repeated pairs of nodes create parallel edges
+    pub fn from_edges(edges: impl IntoIterator<Item = (N, N, T)>) -> Self {
+        let mut g = Self::default();
+
+        for (from, to, weight) in edges {
+            let from_idx = g.nodes.get_or_insert(from);
+            let to_idx = g.nodes.get_or_insert(to);
+            g.extend_capacity_if_needed(cmp::max(from_idx, to_idx));
+            g.add_edge(from_idx, to_idx, weight);
+        }
+
+        g
+    }
+}
+
+impl<N, T> MultiMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    /// Adds new node in graph
+    ///
+    /// Returns index of new node
+    ///
+    /// Computes in **O(1)** (average amortized)
+    /// Worse case **O(n ^ 2)** where n is nodes count
+    ///
+    /// **Panics** if node already exists
+    pub fn add_node(&mut self, node: N) -> usize {
+        let idx = self.nodes.add(node);
+        self.extend_capacity_if_needed(idx);
+        idx
+    }
+
+    /// Removes node and all edges for it
+    ///
+    /// Returns removed node or None, if node not found
+    ///
+    /// Computes in **O(n + e)** where n = nodes count, e = node's edges count
+    pub fn remove_node(&mut self, node_index: usize) -> Option<N> {
+        self.nodes.get_node_by_index(node_index)?;
+
+        for i in 0..self.nodes.bound() {
+            self.remove_edges(node_index, i);
+            self.remove_edges(i, node_index);
+        }
+
+        self.nodes.remove(node_index)
+    }
+
+    /// Adds edge between two nodes, even if they are already connected
+    ///
+    /// Returns id of new edge
+    ///
+    /// Computes in **O(1)** (average amortized)
+    ///
+    /// **Panics** if some of nodes not exists
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: T) -> EdgeId {
+        let missing = [from, to]
+            .into_iter()
+            .filter(|idx| self.nodes.get_node_by_index(*idx).is_none())
+            .max();
+
+        if let Some(idx) = missing {
+            panic!("Can't add edge for not existing node with index {}", idx);
+        }
+
+        let edge = Some(MultiEdge { from, to, weight });
+        let index = match self.free_edges.pop() {
+            Some(index) => {
+                self.edges[index].edge = edge;
+                index
+            }
+            None => {
+                self.edges.push(EdgeSlot {
+                    generation: 0,
+                    edge,
+                });
+                self.edges.len() - 1
+            }
+        };
+
+        let id = EdgeId {
+            index,
+            generation: self.edges[index].generation,
+        };
+        self.adjacency[from][to].push(id);
+        self.edge_count += 1;
+        id
+    }
+
+    /// Removes single edge by it's id
+    ///
+    /// Returns edge's weight if removed else None
+    ///
+    /// Computes in **O(p)** where p = parallel edges count
+    pub fn remove_edge(&mut self, id: EdgeId) -> Option<T> {
+        let edge = self.take_edge(id)?;
+        self.adjacency[edge.from][edge.to].retain(|e| *e != id);
+        self.edge_count -= 1;
+        Some(edge.weight)
+    }
+
+    /// Removes all edges between two nodes
+    ///
+    /// Returns weights of removed edges in order of adding
+    ///
+    /// Computes in **O(p)** where p = parallel edges count
+    pub fn remove_edges(&mut self, from: usize, to: usize) -> Vec<T> {
+        let ids = match self.adjacency.get_mut(from).and_then(|row| row.get_mut(to)) {
+            Some(ids) => std::mem::take(ids),
+            None => return Vec::new(),
+        };
+
+        self.edge_count -= ids.len();
+        ids.into_iter()
+            .filter_map(|id| self.take_edge(id))
+            .map(|edge| edge.weight)
+            .collect()
+    }
+
+    /// Returns weight of edge by it's id or None if not found
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn get_edge(&self, id: EdgeId) -> Option<&T> {
+        self.edge(id).map(|edge| &edge.weight)
+    }
+
+    /// Returns indices of nodes connected by edge or None if not found
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn edge_endpoints(&self, id: EdgeId) -> Option<(usize, usize)> {
+        self.edge(id).map(|edge| (edge.from, edge.to))
+    }
+
+    /// Returns iterator over all parallel edges between two nodes
+    /// in order of adding
+    #[inline]
+    pub fn edges_between(&self, from: usize, to: usize) -> ParallelEdgesIterator<'_, T> {
+        let ids = self
+            .adjacency
+            .get(from)
+            .and_then(|row| row.get(to))
+            .map_or(&[][..], |ids| &ids[..]);
+
+        ParallelEdgesIterator {
+            ids: ids.iter(),
+            edges: &self.edges,
+        }
+    }
+
+    /// Returns count of nodes
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns count of edges including parallel ones
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Returns upper bound of node indices
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn node_bound(&self) -> usize {
+        self.nodes.bound()
+    }
+
+    /// Returns iterator over nodes
+    #[inline]
+    pub fn node_iter(&self) -> NodeIterator<'_, N> {
+        NodeIterator::new(&self.nodes)
+    }
+
+    /// Checks if node exists in graph
+    ///
+    /// Computes in **O(1)** (average)
+    #[inline]
+    pub fn contains_node(&self, node: &N) -> bool {
+        self.nodes.contains(node)
+    }
+
+    /// Checks if at least one edge between two nodes exists
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn contains_edge(&self, from: usize, to: usize) -> bool {
+        self.edges_between(from, to).next().is_some()
+    }
+
+    /// Returns index of node or None if not found
+    ///
+    /// Computes in **O(1)** (average)
+    #[inline]
+    pub fn get_index_of(&self, node: &N) -> Option<usize> {
+        self.nodes.get_index_of(node)
+    }
+
+    fn edge(&self, id: EdgeId) -> Option<&MultiEdge<T>> {
+        let slot = self.edges.get(id.index)?;
+        match slot.generation == id.generation {
+            true => slot.edge.as_ref(),
+            false => None,
+        }
+    }
+
+    /// Takes edge out of slot and puts slot to the free list
+    fn take_edge(&mut self, id: EdgeId) -> Option<MultiEdge<T>> {
+        let slot = self.edges.get_mut(id.index)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+
+        let edge = slot.edge.take()?;
+        slot.generation += 1;
+        self.free_edges.push(id.index);
+        Some(edge)
+    }
+
+    fn extend_capacity_if_needed(&mut self, idx: usize) {
+        if idx < self.adjacency.len() {
+            return;
+        }
+
+        let new_capacity = cmp::max(4, idx + 1).next_power_of_two();
+
+        for row in self.adjacency.iter_mut() {
+            row.resize_with(new_capacity, Vec::new);
+        }
+
+        self.adjacency.resize_with(new_capacity, || {
+            (0..new_capacity).map(|_| Vec::new()).collect()
+        });
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////////////
+
+impl<N, T> GetNodeByIndex<N> for MultiMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    #[inline]
+    fn get_node_by_index(&self, node_idx: usize) -> Option<&N> {
+        self.nodes.get_node_by_index(node_idx)
+    }
+}
+
+impl<N, T> NodeBound for MultiMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    #[inline]
+    fn node_bound(&self) -> usize {
+        self.nodes.bound()
+    }
+}
+
+impl<N, T> GetEdgeByIndex<T> for MultiMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    /// Returns weight of the earliest added edge between two nodes
+    #[inline]
+    fn get_edge_by_index(&self, from: usize, to: usize) -> Option<&T> {
+        self.edges_between(from, to)
+            .next()
+            .map(|(_, weight)| weight)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Iterates over parallel edges between two nodes
+pub struct ParallelEdgesIterator<'a, T> {
+    ids: slice::Iter<'a, EdgeId>,
+    edges: &'a [EdgeSlot<T>],
+}
+
+impl<'a, T> Iterator for ParallelEdgesIterator<'a, T> {
+    type Item = (EdgeId, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.ids.next()?;
+        let edge = self.edges[id.index].edge.as_ref()?;
+        Some((*id, &edge.weight))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+pub struct MultiMatrixGraphNeighborsIterator<'a, N>
+where
+    N: MatrixGraphNode,
+{
+    column: usize,
+    nodes: &'a NodeStorage<N>,
+    adjacency: &'a [Vec<EdgeId>],
+}
+
+impl<'a, N> Iterator for MultiMatrixGraphNeighborsIterator<'a, N>
+where
+    N: MatrixGraphNode,
+{
+    type Item = (usize, &'a N);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.column >= self.adjacency.len() {
+                return None;
+            }
+
+            let node_exists = !self.adjacency[self.column].is_empty();
+            self.column += 1;
+            if node_exists {
+                return Some((
+                    self.column - 1,
+                    self.nodes.get_node_by_index(self.column - 1)?,
+                ));
+            }
+        }
+    }
+}
+
+/// Iterates over outgoing edges of node including parallel ones
+pub struct MultiMatrixGraphEdgesIterator<'a, T> {
+    ids: Flatten<slice::Iter<'a, Vec<EdgeId>>>,
+    edges: &'a [EdgeSlot<T>],
+}
+
+impl<'a, T> Iterator for MultiMatrixGraphEdgesIterator<'a, T> {
+    type Item = EdgeRef<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.ids.next()?;
+        let edge = self.edges[id.index].edge.as_ref()?;
+        Some(EdgeRef {
+            from: edge.from,
+            to: edge.to,
+            weight: &edge.weight,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl<N, T> Graph for MultiMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    type Node = N;
+    type Weight = T;
+    type Neighbors<'a>
+        = MultiMatrixGraphNeighborsIterator<'a, N>
+    where
+        Self: 'a;
+    type Edges<'a>
+        = MultiMatrixGraphEdgesIterator<'a, T>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Parallel edges are counted separately
+    #[inline]
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Returns each neighbor once, regardless of parallel edges count
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        if self.nodes.get_node_by_index(node).is_none() {
+            panic!("Node with index {} not found", node);
+        }
+
+        MultiMatrixGraphNeighborsIterator {
+            column: 0,
+            nodes: &self.nodes,
+            adjacency: &self.adjacency[node],
+        }
+    }
+
+    /// Yields each parallel edge separately
+    fn edges_from(&self, node: usize) -> Self::Edges<'_> {
+        let row = self.adjacency.get(node).map_or(&[][..], |row| &row[..]);
+
+        MultiMatrixGraphEdgesIterator {
+            ids: row.iter().flatten(),
+            edges: &self.edges,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traversable::BfsIterable;
+
+    fn create_graph() -> MultiMatrixGraph<u32, u32> {
+        MultiMatrixGraph::<u32, u32>::default()
+    }
+
+    #[test]
+    fn test_creates_default_empty() {
+        let g = create_graph();
+        assert_eq!(g.node_count(), 0);
+        assert_eq!(g.edge_count(), 0);
+    }
+
+    #[test]
+    fn test_adds_parallel_edges() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        let first = g.add_edge(a, b, 10);
+        let second = g.add_edge(a, b, 20);
+
+        assert_ne!(first, second);
+        assert_eq!(g.edge_count(), 2);
+        assert_eq!(g.get_edge(first), Some(&10));
+        assert_eq!(g.get_edge(second), Some(&20));
+        assert_eq!(g.get_edge_by_index(a, b), Some(&10));
+        assert_eq!(g.edge_endpoints(second), Some((a, b)));
+
+        let actual = g.edges_between(a, b).collect::<Vec<_>>();
+        assert_eq!(actual, vec![(first, &10), (second, &20)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't add edge for not existing node with index 1")]
+    fn test_panics_on_create_edge_for_not_existing_node() {
+        let mut g = create_graph();
+        g.add_edge(0, 1, 1);
+    }
+
+    #[test]
+    fn test_removes_single_parallel_edge() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        let first = g.add_edge(a, b, 10);
+        let second = g.add_edge(a, b, 20);
+
+        assert_eq!(g.remove_edge(first), Some(10));
+        assert_eq!(g.remove_edge(first), None);
+        assert_eq!(g.get_edge(first), None);
+        assert_eq!(g.get_edge(second), Some(&20));
+        assert_eq!(g.edge_count(), 1);
+        assert!(g.contains_edge(a, b));
+    }
+
+    #[test]
+    fn test_reused_slot_invalidates_stale_id() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        let first = g.add_edge(a, a, 10);
+        g.remove_edge(first);
+        let second = g.add_edge(a, a, 20);
+
+        assert_eq!(first.index(), second.index());
+        assert_ne!(first, second);
+        assert_eq!(g.get_edge(first), None);
+        assert_eq!(g.get_edge(second), Some(&20));
+    }
+
+    #[test]
+    fn test_reuses_slots_of_removed_edges() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        let first = g.add_edge(a, b, 10);
+        g.add_edge(b, a, 20);
+
+        let mut stale = first;
+        assert_eq!(g.remove_edge(first), Some(10));
+        for weight in 0..100 {
+            let id = g.add_edge(a, b, weight);
+            assert_eq!(id.index(), first.index());
+            assert_eq!(g.get_edge(stale), None);
+            assert_eq!(g.remove_edges(a, b), vec![weight]);
+            stale = id;
+        }
+
+        assert_eq!(g.remove_edge(first), None);
+        assert_eq!(g.get_edge_by_index(b, a), Some(&20));
+        assert_eq!(g.edge_count(), 1);
+        assert_eq!(g.edges.len(), 2);
+    }
+
+    #[test]
+    fn test_removes_all_edges_between_nodes() {
+        let mut g = MultiMatrixGraph::<u32, u32>::from_edges([(1, 2, 1), (1, 2, 2), (2, 1, 3)]);
+
+        assert_eq!(g.remove_edges(0, 1), vec![1, 2]);
+        assert_eq!(g.edge_count(), 1);
+        assert!(!g.contains_edge(0, 1));
+        assert!(g.contains_edge(1, 0));
+    }
+
+    #[test]
+    fn test_removes_node_with_parallel_edges() {
+        let mut g =
+            MultiMatrixGraph::<u32, u32>::from_edges([(1, 2, 1), (1, 2, 2), (2, 3, 3), (3, 1, 4)]);
+
+        assert_eq!(g.remove_node(0), Some(1));
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_neighbors_are_not_repeated() {
+        let g = MultiMatrixGraph::<u32, u32>::from_edges([(1, 2, 1), (1, 2, 2), (1, 3, 3)]);
+        let actual = g.neighbors(0).map(|(_, node)| *node).collect::<Vec<_>>();
+        assert_eq!(actual, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_edges_from_yields_parallel_edges() {
+        let g = MultiMatrixGraph::<u32, u32>::from_edges([(1, 2, 1), (1, 2, 2), (1, 3, 3)]);
+        let actual = g
+            .edges_from(0)
+            .map(|edge| (edge.to, *edge.weight))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![(1, 1), (1, 2), (2, 3)]);
+        assert_eq!(g.edges_from(5).count(), 0);
+    }
+
+    #[test]
+    fn test_bfs_iter() {
+        let g = MultiMatrixGraph::<u8, ()>::from_edges([(1, 2, ()), (1, 2, ()), (2, 3, ())]);
+        let actual = g.bfs_iter(0).map(|entry| *entry.node).collect::<Vec<_>>();
+        assert_eq!(actual, vec![1, 2, 3]);
+    }
+}
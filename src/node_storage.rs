@@ -1,72 +1,149 @@
-use crate::types::GetNodeByIndex;
-use crate::types::MatrixGraphNode;
+use crate::error::GraphError;
+use crate::types::{GetNodeByIndex, MatrixGraphNode, NodeIndex};
 use std::{
-    collections::{hash_map::DefaultHasher, HashMap, VecDeque},
-    hash::Hasher,
+    collections::{hash_map::DefaultHasher, hash_map::Entry, HashMap, VecDeque},
+    hash::{BuildHasher, BuildHasherDefault, Hasher},
     mem,
+    ops::{Deref, DerefMut},
+    thread,
 };
 
+/// Hasher used by default for nodes
+pub type DefaultBuildHasher = BuildHasherDefault<DefaultHasher>;
+
+/// Map keyed by already calculated node hashes
+type HashIndex<V> = HashMap<u64, V, BuildHasherDefault<PrecomputedHasher>>;
+
+/// Hasher which passes through already calculated hash
+#[derive(Default)]
+struct PrecomputedHasher(u64);
+
+impl Hasher for PrecomputedHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = self.0.rotate_left(8) ^ u64::from(*byte);
+        }
+    }
+
+    fn write_u64(&mut self, hash: u64) {
+        self.0 = hash;
+    }
+}
+
 /// Collection for storing nodes
 /// Works like indexed HashSet
+///
+/// Nodes are found by hash and then compared with `Eq`,
+/// so nodes with colliding hashes are still distinct
+///
+/// Hashes are calculated with `S`, which can be replaced
+/// with faster or deterministic hasher
 #[derive(Debug, PartialEq, Eq)]
-pub struct NodeStorage<N>
+pub struct NodeStorage<N, S = DefaultBuildHasher>
 where
     N: MatrixGraphNode,
 {
     nodes: Vec<Option<N>>,
-    hashes: HashMap<u64, usize>,
+    generations: Vec<usize>,
+    hashes: HashIndex<usize>,
+    collisions: HashIndex<Vec<usize>>,
     removed: VecDeque<usize>,
+    hasher: S,
 }
 
-impl<N> Default for NodeStorage<N>
+impl<N, S> Default for NodeStorage<N, S>
 where
     N: MatrixGraphNode,
+    S: BuildHasher + Default,
 {
     fn default() -> Self {
-        Self {
-            nodes: Vec::new(),
-            removed: VecDeque::new(),
-            hashes: HashMap::new(),
-        }
+        Self::with_hasher(S::default())
     }
 }
 
-impl<N> NodeStorage<N>
+impl<N, S> NodeStorage<N, S>
 where
     N: MatrixGraphNode,
+    S: BuildHasher,
 {
+    /// Creates empty storage which uses given hasher for nodes
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            nodes: Vec::new(),
+            generations: Vec::new(),
+            removed: VecDeque::new(),
+            hashes: HashIndex::default(),
+            collisions: HashIndex::default(),
+            hasher,
+        }
+    }
+
+    /// **Panics** if node already exists
     pub fn add(&mut self, node: N) -> usize {
-        let hash = Self::calculate_hash(&node);
-        if self.hashes.get(&hash).is_some() {
-            panic!("Nodes should be unique.");
+        self.try_add(node).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Adds node or returns error if it already exists
+    pub fn try_add(&mut self, node: N) -> Result<usize, GraphError> {
+        match self.lookup(&node) {
+            Ok(_) => Err(GraphError::DuplicateNode),
+            Err(hash) => Ok(self.insert_hashed(hash, node)),
+        }
+    }
+
+    /// Looks node up hashing it once
+    ///
+    /// Returns index of node if found,
+    /// else hash of node which can be passed to [`NodeStorage::insert_hashed`]
+    pub(crate) fn lookup(&self, node: &N) -> Result<usize, u64> {
+        let hash = self.calculate_hash(node);
+        self.find(hash, node).ok_or(hash)
+    }
+
+    /// Returns index of node, adding it if missing
+    ///
+    /// Node is hashed only once whether it exists or not
+    pub(crate) fn get_or_insert(&mut self, node: N) -> usize {
+        match self.lookup(&node) {
+            Ok(idx) => idx,
+            Err(hash) => self.insert_hashed(hash, node),
         }
+    }
 
-        match self.removed.pop_back() {
+    /// Adds node with precalculated hash
+    ///
+    /// Node must not exist in storage
+    pub(crate) fn insert_hashed(&mut self, hash: u64, node: N) -> usize {
+        let idx = match self.removed.pop_back() {
             Some(idx) => {
-                let _ = mem::replace(&mut self.nodes[idx], Some(node));
-                self.hashes.insert(hash, idx);
+                let _ = self.nodes[idx].replace(node);
                 idx
             }
             None => {
                 self.nodes.push(Some(node));
-                let idx = self.nodes.len() - 1;
-                self.hashes.insert(hash, idx);
-                idx
+                if self.generations.len() < self.nodes.len() {
+                    self.generations.push(0);
+                }
+                self.nodes.len() - 1
             }
-        }
+        };
+
+        self.insert_hash(hash, idx);
+        idx
     }
 
+    /// Removes node and invalidates all handles to it
     pub fn remove(&mut self, idx: usize) -> Option<N> {
-        if idx >= self.len() {
-            return None;
-        }
-        let node = mem::replace(&mut self.nodes[idx], None);
-        if let Some(node) = node.as_ref() {
-            let hash = Self::calculate_hash(node);
-            self.hashes.remove(&hash);
-        }
+        let node = self.nodes.get_mut(idx)?.take()?;
+        let hash = self.calculate_hash(&node);
+        self.remove_hash(hash, idx);
+        self.generations[idx] += 1;
         self.removed.push_back(idx);
-        node
+        Some(node)
     }
 
     pub fn len(&self) -> usize {
@@ -77,16 +154,61 @@ where
         self.len() == 0
     }
 
+    /// Returns upper bound of node indices, including removed slots
+    pub fn bound(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// **Panics** if node not exists
     pub fn get(&self, idx: usize) -> &N {
-        if idx > self.nodes.len() {
-            panic!("Out of bounds");
+        self.try_get(idx).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Returns node or error if index is out of bounds or node was removed
+    pub fn try_get(&self, idx: usize) -> Result<&N, GraphError> {
+        match self.nodes.get(idx) {
+            Some(Some(node)) => Ok(node),
+            Some(None) => Err(GraphError::RemovedNode(idx)),
+            None => Err(GraphError::NodeNotFound(idx)),
         }
+    }
 
-        let node = self.nodes[idx].as_ref();
+    /// Returns guard for changing node in place
+    /// or None if node not exists
+    ///
+    /// Changes should be applied with [`NodeMut::commit`]
+    pub fn get_mut(&mut self, idx: usize) -> Option<NodeMut<'_, N, S>>
+    where
+        N: Clone,
+    {
+        let node = self.nodes.get(idx)?.as_ref()?;
+        let hash = self.calculate_hash(node);
+        let original = Some(node.clone());
+
+        Some(NodeMut {
+            storage: self,
+            idx,
+            hash,
+            original,
+        })
+    }
 
-        match node {
-            Some(node) => node,
-            None => panic!("Trying to get removed node"),
+    /// Replaces node keeping it's index
+    ///
+    /// Returns old node or error if node not exists
+    /// or another node is equal to the new one
+    pub fn try_replace(&mut self, idx: usize, node: N) -> Result<N, GraphError> {
+        let old_hash = self.calculate_hash(self.try_get(idx)?);
+        let hash = self.calculate_hash(&node);
+
+        match self.find(hash, &node) {
+            Some(existing) if existing != idx => Err(GraphError::DuplicateNode),
+            _ => {
+                self.remove_hash(old_hash, idx);
+                let old = self.nodes[idx].replace(node).expect("Node should exist");
+                self.insert_hash(hash, idx);
+                Ok(old)
+            }
         }
     }
 
@@ -95,18 +217,195 @@ where
     }
 
     pub fn get_index_of(&self, node: &N) -> Option<usize> {
-        let hash = Self::calculate_hash(node);
-        self.hashes.get(&hash).cloned()
+        self.find(self.calculate_hash(node), node)
+    }
+
+    /// Moves all nodes to the front keeping their order
+    ///
+    /// Returns map from old indices to new ones,
+    /// where removed slots are mapped to None
+    ///
+    /// Handles of moved nodes become stale
+    pub fn compact(&mut self) -> Vec<Option<usize>> {
+        let mut map = vec![None; self.nodes.len()];
+        let mut nodes = Vec::with_capacity(self.len());
+
+        for (idx, node) in mem::take(&mut self.nodes).into_iter().enumerate() {
+            if let Some(node) = node {
+                if nodes.len() != idx {
+                    self.generations[idx] += 1;
+                }
+                map[idx] = Some(nodes.len());
+                nodes.push(Some(node));
+            }
+        }
+
+        let remap = |idx: &mut usize| *idx = map[*idx].expect("Hashed node should exist");
+        self.hashes.values_mut().for_each(remap);
+        self.collisions.values_mut().flatten().for_each(remap);
+
+        self.nodes = nodes;
+        self.removed.clear();
+        map
+    }
+
+    /// Returns generational handle of existing node
+    pub fn handle(&self, idx: usize) -> Option<NodeIndex> {
+        self.get_node_by_index(idx)?;
+        Some(NodeIndex::new(idx, self.generations[idx]))
+    }
+
+    /// Returns index of node behind handle
+    /// or None if node was removed after handle creation
+    pub fn resolve(&self, handle: NodeIndex) -> Option<usize> {
+        let idx = handle.index();
+        self.get_node_by_index(idx)?;
+
+        match self.generations[idx] == handle.generation() {
+            true => Some(idx),
+            false => None,
+        }
     }
 
     pub fn iter(&'_ self) -> NodeStorageIterator<'_, N> {
         NodeStorageIterator::new(&self.nodes)
     }
 
-    fn calculate_hash(node: &N) -> u64 {
-        let mut s = DefaultHasher::new();
-        node.hash(&mut s);
-        s.finish()
+    fn find(&self, hash: u64, node: &N) -> Option<usize> {
+        let is_same = |idx: &usize| self.nodes[*idx].as_ref() == Some(node);
+
+        let first = self.hashes.get(&hash)?;
+        if is_same(first) {
+            return Some(*first);
+        }
+
+        self.collisions.get(&hash)?.iter().copied().find(is_same)
+    }
+
+    fn insert_hash(&mut self, hash: u64, idx: usize) {
+        match self.hashes.entry(hash) {
+            Entry::Occupied(_) => self.collisions.entry(hash).or_default().push(idx),
+            Entry::Vacant(entry) => {
+                entry.insert(idx);
+            }
+        }
+    }
+
+    fn remove_hash(&mut self, hash: u64, idx: usize) {
+        if self.hashes.get(&hash) == Some(&idx) {
+            match self.collisions.get_mut(&hash).and_then(|c| c.pop()) {
+                Some(next) => self.hashes.insert(hash, next),
+                None => self.hashes.remove(&hash),
+            };
+        } else if let Some(collisions) = self.collisions.get_mut(&hash) {
+            collisions.retain(|i| *i != idx);
+        }
+
+        if self.collisions.get(&hash).is_some_and(|c| c.is_empty()) {
+            self.collisions.remove(&hash);
+        }
+    }
+
+    fn calculate_hash(&self, node: &N) -> u64 {
+        self.hasher.hash_one(node)
+    }
+}
+
+/// Mutable access to node in storage
+///
+/// Changes are applied by [`NodeMut::commit`], which re-hashes node,
+/// so it can still be found by value even if parts of it affecting the hash
+/// were changed. If changed node equals to another node in storage,
+/// original node is restored and error is returned
+///
+/// Dropped guard applies changes the same way,
+/// but duplicate is reported only by debug assertion
+pub struct NodeMut<'a, N, S = DefaultBuildHasher>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    storage: &'a mut NodeStorage<N, S>,
+    idx: usize,
+    hash: u64,
+    original: Option<N>,
+}
+
+impl<'a, N, S> NodeMut<'a, N, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    /// Applies changes of node
+    ///
+    /// Returns error and restores original node
+    /// if changed node equals to another node in storage
+    pub fn commit(mut self) -> Result<(), GraphError> {
+        self.apply()
+    }
+
+    fn apply(&mut self) -> Result<(), GraphError> {
+        let Some(original) = self.original.take() else {
+            return Ok(());
+        };
+
+        let storage = &mut *self.storage;
+        storage.remove_hash(self.hash, self.idx);
+
+        let node = storage.nodes[self.idx]
+            .as_ref()
+            .expect("Borrowed node should exist");
+        let hash = storage.calculate_hash(node);
+
+        if storage.find(hash, node).is_some() {
+            storage.nodes[self.idx] = Some(original);
+            storage.insert_hash(self.hash, self.idx);
+            return Err(GraphError::DuplicateNode);
+        }
+
+        storage.insert_hash(hash, self.idx);
+        Ok(())
+    }
+}
+
+impl<'a, N, S> Deref for NodeMut<'a, N, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    type Target = N;
+
+    fn deref(&self) -> &N {
+        self.storage.nodes[self.idx]
+            .as_ref()
+            .expect("Borrowed node should exist")
+    }
+}
+
+impl<'a, N, S> DerefMut for NodeMut<'a, N, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    fn deref_mut(&mut self) -> &mut N {
+        self.storage.nodes[self.idx]
+            .as_mut()
+            .expect("Borrowed node should exist")
+    }
+}
+
+impl<'a, N, S> Drop for NodeMut<'a, N, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    fn drop(&mut self) {
+        let applied = self.apply();
+        debug_assert!(
+            applied.is_ok() || thread::panicking(),
+            "{}",
+            GraphError::DuplicateNode
+        );
     }
 }
 
@@ -139,12 +438,12 @@ impl<'a, N> Iterator for NodeStorageIterator<'a, N> {
     }
 }
 
-impl<N> GetNodeByIndex<N> for NodeStorage<N>
+impl<N, S> GetNodeByIndex<N> for NodeStorage<N, S>
 where
     N: MatrixGraphNode,
 {
     fn get_node_by_index(&self, node_idx: usize) -> Option<&N> {
-        if node_idx > self.nodes.len() {
+        if node_idx >= self.nodes.len() {
             return None;
         }
 
@@ -160,6 +459,23 @@ mod tests {
         NodeStorage::<u32>::default()
     }
 
+    /// Node which hash depends only on it's group
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Colliding {
+        group: u8,
+        value: u32,
+    }
+
+    impl std::hash::Hash for Colliding {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.group.hash(state);
+        }
+    }
+
+    fn colliding(group: u8, value: u32) -> Colliding {
+        Colliding { group, value }
+    }
+
     #[test]
     fn test_add_new_node() {
         let mut ns = create_node_storage();
@@ -190,6 +506,35 @@ mod tests {
         ns.add(54);
     }
 
+    #[test]
+    fn test_try_add_returns_error_on_existing_node() {
+        let mut ns = create_node_storage();
+        assert_eq!(ns.try_add(54), Ok(0));
+        assert_eq!(ns.try_add(54), Err(GraphError::DuplicateNode));
+        assert_eq!(ns.len(), 1);
+    }
+
+    #[test]
+    fn test_try_get_distinguishes_removed_and_missing_nodes() {
+        let mut ns = create_node_storage();
+        ns.add(1);
+        ns.add(2);
+        ns.remove(0);
+
+        assert_eq!(ns.try_get(1), Ok(&2));
+        assert_eq!(ns.try_get(0), Err(GraphError::RemovedNode(0)));
+        assert_eq!(ns.try_get(2), Err(GraphError::NodeNotFound(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Node with index 0 was removed")]
+    fn test_get_panics_on_removed_node() {
+        let mut ns = create_node_storage();
+        ns.add(1);
+        ns.remove(0);
+        ns.get(0);
+    }
+
     #[test]
     fn test_remove_returns_node() {
         let mut ns = create_node_storage();
@@ -208,7 +553,7 @@ mod tests {
     #[test]
     fn test_get_index_of_returns_correct_index() {
         let mut ns = create_node_storage();
-        let nodes = vec![134, 235, 2342, 2123, 543];
+        let nodes = [134, 235, 2342, 2123, 543];
         for (idx, node) in nodes.iter().enumerate() {
             ns.add(*node);
             assert_eq!(ns.get_index_of(node).unwrap(), idx);
@@ -218,7 +563,7 @@ mod tests {
     #[test]
     fn test_iter_iterates_over_all_some_elements() {
         let mut ns = create_node_storage();
-        let nodes = vec![123, 123123, 213533, 234, 1254];
+        let nodes = [123, 123123, 213533, 234, 1254];
         for node in nodes.iter() {
             ns.add(*node);
         }
@@ -230,7 +575,7 @@ mod tests {
     #[test]
     fn test_get_node_by_index() {
         let mut ns = create_node_storage();
-        let nodes = vec![54, 78, 45, 123, 902];
+        let nodes = [54, 78, 45, 123, 902];
         for node in nodes.iter() {
             ns.add(*node);
         }
@@ -240,4 +585,241 @@ mod tests {
             assert_eq!(node, actual);
         }
     }
+
+    #[test]
+    fn test_get_node_by_index_returns_none_out_of_bounds() {
+        let mut ns = create_node_storage();
+        ns.add(54);
+        assert!(ns.get_node_by_index(1).is_none());
+    }
+
+    #[test]
+    fn test_remove_twice_returns_none() {
+        let mut ns = create_node_storage();
+        ns.add(1);
+        ns.add(2);
+        assert_eq!(ns.remove(0), Some(1));
+        assert_eq!(ns.remove(0), None);
+        assert_eq!(ns.len(), 1);
+    }
+
+    #[test]
+    fn test_resolves_live_handle() {
+        let mut ns = create_node_storage();
+        ns.add(1);
+        let idx = ns.add(2);
+        let handle = ns.handle(idx).unwrap();
+        assert_eq!(ns.resolve(handle), Some(idx));
+    }
+
+    #[test]
+    fn test_not_resolves_stale_handle() {
+        let mut ns = create_node_storage();
+        let idx = ns.add(1);
+        let handle = ns.handle(idx).unwrap();
+        ns.remove(idx);
+        assert_eq!(ns.add(2), idx);
+
+        assert!(ns.resolve(handle).is_none());
+        assert_eq!(ns.resolve(ns.handle(idx).unwrap()), Some(idx));
+    }
+
+    #[test]
+    fn test_distinguishes_nodes_with_colliding_hashes() {
+        let mut ns = NodeStorage::<Colliding>::default();
+        let a = ns.add(colliding(0, 1));
+        let b = ns.add(colliding(0, 2));
+        let c = ns.add(colliding(0, 3));
+
+        assert_eq!(ns.len(), 3);
+        assert_eq!(ns.get_index_of(&colliding(0, 1)), Some(a));
+        assert_eq!(ns.get_index_of(&colliding(0, 2)), Some(b));
+        assert_eq!(ns.get_index_of(&colliding(0, 3)), Some(c));
+        assert!(!ns.contains(&colliding(0, 4)));
+    }
+
+    #[test]
+    fn test_removes_nodes_with_colliding_hashes() {
+        let mut ns = NodeStorage::<Colliding>::default();
+        let a = ns.add(colliding(0, 1));
+        let b = ns.add(colliding(0, 2));
+        let c = ns.add(colliding(0, 3));
+
+        ns.remove(a);
+        assert!(!ns.contains(&colliding(0, 1)));
+        assert_eq!(ns.get_index_of(&colliding(0, 2)), Some(b));
+        assert_eq!(ns.get_index_of(&colliding(0, 3)), Some(c));
+
+        ns.remove(c);
+        assert_eq!(ns.get_index_of(&colliding(0, 2)), Some(b));
+
+        ns.remove(b);
+        assert!(ns.is_empty());
+        assert_eq!(ns.add(colliding(0, 2)), b);
+    }
+
+    #[test]
+    #[should_panic(expected = "Nodes should be unique.")]
+    fn test_panics_on_adding_existing_colliding_node() {
+        let mut ns = NodeStorage::<Colliding>::default();
+        ns.add(colliding(0, 1));
+        ns.add(colliding(0, 2));
+        ns.add(colliding(0, 2));
+    }
+
+    #[test]
+    fn test_compact_moves_nodes_to_front() {
+        let mut ns = create_node_storage();
+        for node in [10, 20, 30, 40] {
+            ns.add(node);
+        }
+        ns.remove(0);
+        ns.remove(2);
+
+        let map = ns.compact();
+
+        assert_eq!(map, vec![None, Some(0), None, Some(1)]);
+        assert_eq!(ns.bound(), 2);
+        assert_eq!(ns.len(), 2);
+        assert_eq!(ns.get_index_of(&20), Some(0));
+        assert_eq!(ns.get_index_of(&40), Some(1));
+        assert_eq!(ns.add(50), 2);
+    }
+
+    #[test]
+    fn test_compact_invalidates_handles_of_moved_nodes() {
+        let mut ns = create_node_storage();
+        let a = ns.add(10);
+        let b = ns.add(20);
+        let c = ns.add(30);
+        let kept = ns.handle(a).unwrap();
+        let moved = ns.handle(c).unwrap();
+        ns.remove(b);
+
+        ns.compact();
+        let added = ns.add(40);
+
+        assert_eq!(ns.resolve(kept), Some(a));
+        assert!(ns.resolve(moved).is_none());
+        assert_eq!(added, c);
+        assert_eq!(ns.resolve(ns.handle(added).unwrap()), Some(added));
+    }
+
+    #[test]
+    fn test_compact_keeps_colliding_nodes() {
+        let mut ns = NodeStorage::<Colliding>::default();
+        ns.add(colliding(1, 0));
+        ns.add(colliding(0, 1));
+        ns.add(colliding(0, 2));
+        ns.remove(0);
+
+        ns.compact();
+
+        assert_eq!(ns.get_index_of(&colliding(0, 1)), Some(0));
+        assert_eq!(ns.get_index_of(&colliding(0, 2)), Some(1));
+    }
+
+    #[test]
+    fn test_uses_custom_hasher() {
+        let mut ns =
+            NodeStorage::<u32, _>::with_hasher(std::collections::hash_map::RandomState::new());
+        let a = ns.add(1);
+        let b = ns.add(2);
+        assert_eq!(ns.get_index_of(&1), Some(a));
+        assert_eq!(ns.get_index_of(&2), Some(b));
+    }
+
+    #[test]
+    fn test_bound_includes_removed_slots() {
+        let mut ns = create_node_storage();
+        ns.add(1);
+        ns.add(2);
+        ns.remove(0);
+        assert_eq!(ns.len(), 1);
+        assert_eq!(ns.bound(), 2);
+    }
+
+    #[test]
+    fn test_get_mut_rehashes_changed_node() {
+        let mut ns = create_node_storage();
+        ns.add(1);
+        let idx = ns.add(2);
+
+        *ns.get_mut(idx).unwrap() = 20;
+
+        assert!(!ns.contains(&2));
+        assert_eq!(ns.get_index_of(&20), Some(idx));
+        assert_eq!(ns.get(idx), &20);
+    }
+
+    #[test]
+    fn test_get_mut_keeps_colliding_nodes_findable() {
+        let mut ns = NodeStorage::<Colliding>::default();
+        let a = ns.add(colliding(1, 1));
+        let b = ns.add(colliding(1, 2));
+
+        ns.get_mut(a).unwrap().value = 3;
+
+        assert_eq!(ns.get_index_of(&colliding(1, 3)), Some(a));
+        assert_eq!(ns.get_index_of(&colliding(1, 2)), Some(b));
+        assert!(!ns.contains(&colliding(1, 1)));
+    }
+
+    #[test]
+    fn test_get_mut_returns_none_for_removed_node() {
+        let mut ns = create_node_storage();
+        let idx = ns.add(1);
+        ns.remove(idx);
+        assert!(ns.get_mut(idx).is_none());
+        assert!(ns.get_mut(10).is_none());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Nodes should be unique.")]
+    fn test_dropped_guard_asserts_on_duplicate() {
+        let mut ns = create_node_storage();
+        ns.add(1);
+        let idx = ns.add(2);
+        *ns.get_mut(idx).unwrap() = 1;
+    }
+
+    #[test]
+    fn test_get_mut_restores_node_on_duplicate() {
+        let mut ns = create_node_storage();
+        ns.add(1);
+        let idx = ns.add(2);
+
+        let mut node = ns.get_mut(idx).unwrap();
+        *node = 1;
+        assert_eq!(node.commit(), Err(GraphError::DuplicateNode));
+        assert_eq!(ns.iter().collect::<Vec<_>>(), vec![&1, &2]);
+        assert_eq!(ns.get_index_of(&1), Some(0));
+    }
+
+    #[test]
+    fn test_commit_applies_changes() {
+        let mut ns = NodeStorage::<Colliding>::default();
+        let a = ns.add(colliding(1, 1));
+        ns.add(colliding(1, 2));
+
+        let mut node = ns.get_mut(a).unwrap();
+        node.value = 3;
+        assert_eq!(node.commit(), Ok(()));
+        assert_eq!(ns.get_index_of(&colliding(1, 3)), Some(a));
+    }
+
+    #[test]
+    fn test_try_replace_keeps_nodes_unique() {
+        let mut ns = create_node_storage();
+        ns.add(1);
+        let idx = ns.add(2);
+
+        assert_eq!(ns.try_replace(idx, 1), Err(GraphError::DuplicateNode));
+        assert_eq!(ns.try_replace(idx, 2), Ok(2));
+        assert_eq!(ns.try_replace(idx, 3), Ok(2));
+        assert_eq!(ns.get_index_of(&3), Some(idx));
+        assert!(!ns.contains(&2));
+        assert_eq!(ns.try_replace(5, 4), Err(GraphError::NodeNotFound(5)));
+    }
 }
@@ -1,5 +1,5 @@
 use crate::matrix_graph::MatrixGraph;
-use crate::types::{AdjacencyMatrix, MatrixGraphNode};
+use crate::types::{Graph, MatrixGraphNode};
 use core::str::FromStr;
 use std::error::Error;
 use std::fmt;
@@ -27,23 +27,45 @@ impl fmt::Display for TgfParseError {
 
 impl Error for TgfParseError {}
 
-pub fn ser_tgf<N, T>(input: &AdjacencyMatrix<N, T>) -> String
+/// Serializes graph into Trivial Graph Format
+///
+/// Nodes are numbered from 1 in order of their indices,
+/// so removed nodes don't leave gaps in output
+///
+/// Edges of undirected graph are written once
+pub fn ser_tgf<G>(input: &G) -> String
 where
-    N: MatrixGraphNode + fmt::Display,
-    T: fmt::Display,
+    G: Graph,
+    G::Node: fmt::Display,
+    G::Weight: fmt::Display,
 {
     let mut output = "".to_string();
-
-    for (i, node) in input.nodes.iter().enumerate() {
-        output += &format!("{} {}\n", i + 1, node);
+    let mut ids = vec![None; input.node_bound()];
+    let mut next_id = 1;
+
+    for (i, id) in ids.iter_mut().enumerate() {
+        if let Some(node) = input.get_node_by_index(i) {
+            output += &format!("{} {}\n", next_id, node);
+            *id = Some(next_id);
+            next_id += 1;
+        }
     }
 
     output += "#\n";
 
-    for i in 0..input.nodes.len() {
-        for j in 0..input.nodes.len() {
-            if let Some(weight) = &input.edges[i][j] {
-                output += &format!("{} {} {}\n", i + 1, j + 1, weight);
+    for (i, from_id) in ids.iter().enumerate() {
+        let from_id = match from_id {
+            Some(id) => id,
+            None => continue,
+        };
+
+        for edge in input.edges_from(i) {
+            if !input.is_directed() && edge.to < i {
+                continue;
+            }
+
+            if let Some(to_id) = ids[edge.to] {
+                output += &format!("{} {} {}\n", from_id, to_id, edge.weight);
             }
         }
     }
@@ -98,6 +120,7 @@ where
 mod tests {
     use super::*;
     use crate::types::{Adjacency, GetEdgeByIndex};
+    use crate::undirected_matrix_graph::UndirectedMatrixGraph;
 
     #[test]
     fn test_ser_to_tgf() {
@@ -128,11 +151,26 @@ mod tests {
             (3, 1, 4),
         ];
 
-        let g = MatrixGraph::<u32, u8>::from_edges(edges.into_iter());
+        let g = MatrixGraph::<u32, u8>::from_edges(edges);
         let actual = ser_tgf(&g.get_adjacency_matrix());
         assert_eq!(tgf, actual);
     }
 
+    #[test]
+    fn test_ser_undirected_graph_to_tgf() {
+        let tgf = "1 1
+2 2
+3 3
+#
+1 2 3
+2 3 4
+3 3 5
+";
+
+        let g = UndirectedMatrixGraph::<u32, u8>::from_edges([(1, 2, 3), (3, 2, 4), (3, 3, 5)]);
+        assert_eq!(tgf, ser_tgf(&g));
+    }
+
     #[test]
     fn test_de_from_tgf() {
         let tgf = "1 54
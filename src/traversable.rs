@@ -1,73 +1,125 @@
-use crate::types::MatrixGraphNode;
-use crate::types::{GetNodeByIndex, GraphEntry, Neighbors};
+use crate::types::{BfsEntry, EdgeRef, Graph, GraphEntry, MatrixGraphNode};
 use std::collections::HashSet;
 use std::collections::VecDeque;
-use std::marker::PhantomData;
+use std::vec;
 
 /// Boundary for getting iterator over breadth first traverse of graph
-pub trait BfsIterable<'a, N: 'a, I, T, G>
-where
-    N: MatrixGraphNode,
-    I: Iterator<Item = (usize, &'a N)>,
-    G: Neighbors<'a, N, I> + GetNodeByIndex<N>,
-{
-    fn get_graph(&'a self) -> &'a G;
-    fn bfs_iter(&'a self, from: usize) -> BreadthFirstTraverseIterator<'a, N, G, I> {
-        BreadthFirstTraverseIterator::new(self.get_graph(), from)
+///
+/// Implemented for every [`Graph`]
+pub trait BfsIterable: Graph + Sized {
+    fn bfs_iter(&self, from: usize) -> BreadthFirstTraverseIterator<'_, Self> {
+        BreadthFirstTraverseIterator::new(self, from)
+    }
+
+    /// Breadth first traverse starting from all `sources` at once
+    fn bfs_iter_from<I>(&self, sources: I) -> BreadthFirstTraverseIterator<'_, Self>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        BreadthFirstTraverseIterator::with_sources(self, sources)
+    }
+
+    /// Breadth first traverse of whole graph
+    ///
+    /// When reachable nodes are exhausted, traverse restarts
+    /// from unvisited node with the lowest index,
+    /// so disconnected components are visited too
+    fn bfs_iter_all(&self) -> BreadthFirstTraverseIterator<'_, Self> {
+        BreadthFirstTraverseIterator::whole_graph(self)
+    }
+
+    /// Breadth first traverse reporting depth and parent of each node
+    fn bfs_tree_iter(&self, from: usize) -> BreadthFirstTreeIterator<'_, Self> {
+        BreadthFirstTreeIterator::new(self, from)
+    }
+
+    /// Breadth first traverse starting from all `sources` at once,
+    /// where depth is distance to the nearest source
+    fn bfs_tree_iter_from<I>(&self, sources: I) -> BreadthFirstTreeIterator<'_, Self>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        BreadthFirstTreeIterator::with_sources(self, sources)
+    }
+
+    /// Traverses all nodes reachable from `from`
+    /// and collects their distances and parents
+    ///
+    /// Takes **O(b)** space where b = upper bound of node indices
+    fn bfs_tree(&self, from: usize) -> BfsTree {
+        BfsTree::from_iter(self.node_bound(), from, self.bfs_tree_iter(from))
     }
 }
 
+impl<G> BfsIterable for G where G: Graph {}
+
 /// Iterates over breadth first traverse of graph
 /// represented by adjacency list
 ///
 /// Takes **O(n)** space and computes in **O(n + e)**
 /// where n = node count, e = edge count
-pub struct BreadthFirstTraverseIterator<'a, N: 'a, G, I>
+pub struct BreadthFirstTraverseIterator<'a, G>
 where
-    I: Iterator<Item = (usize, &'a N)>,
-    G: Neighbors<'a, N, I> + GetNodeByIndex<N>,
+    G: Graph,
 {
     graph: &'a G,
     visited: HashSet<usize>,
     queue: VecDeque<usize>,
-    phantom1: PhantomData<N>,
-    phantom2: PhantomData<I>,
+    /// Index to search next unvisited node from, if whole graph is traversed
+    restart: Option<usize>,
 }
 
-impl<'a, N, G, I> BreadthFirstTraverseIterator<'a, N, G, I>
+impl<'a, G> BreadthFirstTraverseIterator<'a, G>
 where
-    N: MatrixGraphNode,
-    I: Iterator<Item = (usize, &'a N)>,
-    G: Neighbors<'a, N, I> + GetNodeByIndex<N>,
+    G: Graph,
 {
     pub fn new(graph: &'a G, from: usize) -> Self {
+        Self::with_sources(graph, [from])
+    }
+
+    /// Creates traverse starting from all `sources`, skipping repeated ones
+    pub fn with_sources(graph: &'a G, sources: impl IntoIterator<Item = usize>) -> Self {
+        let mut visited = HashSet::new();
+        let queue = sources
+            .into_iter()
+            .filter(|source| visited.insert(*source))
+            .collect();
+
+        Self {
+            graph,
+            visited,
+            queue,
+            restart: None,
+        }
+    }
+
+    /// Creates traverse over whole graph including disconnected components
+    pub fn whole_graph(graph: &'a G) -> Self {
         Self {
             graph,
             visited: HashSet::new(),
-            queue: VecDeque::from([from]),
-            phantom1: PhantomData,
-            phantom2: PhantomData,
+            queue: VecDeque::new(),
+            restart: Some(0),
         }
     }
 }
 
-impl<'a, N, G, I> Iterator for BreadthFirstTraverseIterator<'a, N, G, I>
+impl<'a, G> Iterator for BreadthFirstTraverseIterator<'a, G>
 where
-    N: MatrixGraphNode,
-    I: Iterator<Item = (usize, &'a N)>,
-    G: Neighbors<'a, N, I> + GetNodeByIndex<N>,
+    G: Graph,
 {
-    type Item = GraphEntry<'a, N>;
+    type Item = GraphEntry<'a, G::Node>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.queue.is_empty() {
-            return None;
-        }
+        let cur = match self.queue.pop_front() {
+            Some(cur) => cur,
+            None => next_root(self.graph, &mut self.visited, &mut self.restart)?,
+        };
 
-        let cur = self.queue.pop_front()?;
-        self.visited.insert(cur);
-
-        let edges = self.graph.neighbors(cur).collect::<Vec<(usize, &N)>>();
+        let edges = self
+            .graph
+            .neighbors(cur)
+            .collect::<Vec<(usize, &G::Node)>>();
         let node = self.graph.get_node_by_index(cur).unwrap();
 
         for (i, _) in edges.iter() {
@@ -83,10 +135,541 @@ where
         })
     }
 }
+
+/// Finds unvisited node with the lowest index not less than `restart`
+/// and marks it visited
+///
+/// Sets `restart` to None when all nodes are visited
+fn next_root<G>(
+    graph: &G,
+    visited: &mut HashSet<usize>,
+    restart: &mut Option<usize>,
+) -> Option<usize>
+where
+    G: Graph,
+{
+    let start = (*restart)?;
+    let root = (start..graph.node_bound())
+        .find(|idx| !visited.contains(idx) && graph.get_node_by_index(*idx).is_some());
+
+    *restart = root.map(|root| root + 1);
+    visited.insert(root?);
+    root
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Iterates over breadth first traverse of graph
+/// tracking depth and parent of each node
+///
+/// Takes **O(n)** space and computes in **O(n + e)**
+/// where n = node count, e = edge count
+pub struct BreadthFirstTreeIterator<'a, G>
+where
+    G: Graph,
+{
+    graph: &'a G,
+    max_depth: Option<usize>,
+    visited: HashSet<usize>,
+    queue: VecDeque<(usize, usize, Option<usize>)>,
+    /// Index to search next unvisited node from, if whole graph is traversed
+    restart: Option<usize>,
+}
+
+impl<'a, G> BreadthFirstTreeIterator<'a, G>
+where
+    G: Graph,
+{
+    pub fn new(graph: &'a G, from: usize) -> Self {
+        Self::with_sources(graph, [from])
+    }
+
+    /// Creates traverse starting from all `sources`, skipping repeated ones
+    pub fn with_sources(graph: &'a G, sources: impl IntoIterator<Item = usize>) -> Self {
+        let mut visited = HashSet::new();
+        let queue = sources
+            .into_iter()
+            .filter(|source| visited.insert(*source))
+            .map(|source| (source, 0, None))
+            .collect();
+
+        Self {
+            graph,
+            max_depth: None,
+            visited,
+            queue,
+            restart: None,
+        }
+    }
+
+    /// Creates traverse over whole graph including disconnected components,
+    /// where each component starts with depth 0
+    pub fn whole_graph(graph: &'a G) -> Self {
+        Self {
+            graph,
+            max_depth: None,
+            visited: HashSet::new(),
+            queue: VecDeque::new(),
+            restart: Some(0),
+        }
+    }
+
+    /// Stops traverse at given depth,
+    /// so nodes further than `depth` edges from start are not visited
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+}
+
+impl<'a, G> Iterator for BreadthFirstTreeIterator<'a, G>
+where
+    G: Graph,
+{
+    type Item = BfsEntry<'a, G::Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (cur, depth, parent) = match self.queue.pop_front() {
+            Some(entry) => entry,
+            None => (
+                next_root(self.graph, &mut self.visited, &mut self.restart)?,
+                0,
+                None,
+            ),
+        };
+
+        let edges = self
+            .graph
+            .neighbors(cur)
+            .collect::<Vec<(usize, &G::Node)>>();
+        let node = self.graph.get_node_by_index(cur).unwrap();
+
+        if self.max_depth.is_none_or(|max| depth < max) {
+            for (i, _) in edges.iter() {
+                if self.visited.insert(*i) {
+                    self.queue.push_back((*i, depth + 1, Some(cur)));
+                }
+            }
+        }
+
+        Some(BfsEntry {
+            index: cur,
+            node,
+            depth,
+            parent,
+            edges: edges.into_iter().map(|(_idx, node)| node).collect(),
+        })
+    }
+}
+
+/// Distances and parents of nodes reachable from start node
+/// collected by breadth first traverse
+///
+/// Distance is count of edges on the shortest path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BfsTree {
+    from: usize,
+    distances: Vec<Option<usize>>,
+    parents: Vec<Option<usize>>,
+}
+
+impl BfsTree {
+    fn from_iter<'a, N>(
+        bound: usize,
+        from: usize,
+        iter: impl Iterator<Item = BfsEntry<'a, N>>,
+    ) -> Self
+    where
+        N: 'a + MatrixGraphNode,
+    {
+        let mut tree = Self {
+            from,
+            distances: vec![None; bound],
+            parents: vec![None; bound],
+        };
+
+        for entry in iter {
+            tree.distances[entry.index] = Some(entry.depth);
+            tree.parents[entry.index] = entry.parent;
+        }
+
+        tree
+    }
+
+    /// Returns index of start node
+    #[inline]
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    /// Returns distance to node or None if node is unreachable
+    #[inline]
+    pub fn distance(&self, node: usize) -> Option<usize> {
+        *self.distances.get(node)?
+    }
+
+    /// Returns parent of node in traverse tree,
+    /// None for start node and unreachable nodes
+    #[inline]
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        *self.parents.get(node)?
+    }
+
+    /// Returns distances indexed by node,
+    /// where unreachable nodes are None
+    #[inline]
+    pub fn distances(&self) -> &[Option<usize>] {
+        &self.distances
+    }
+
+    /// Returns one of the shortest paths from start node to `to`
+    /// including both ends or None if `to` is unreachable
+    ///
+    /// Computes in **O(d)** where d = distance to `to`
+    pub fn path_to(&self, to: usize) -> Option<Vec<usize>> {
+        self.distance(to)?;
+
+        let mut path = vec![to];
+        while let Some(parent) = self.parent(*path.last()?) {
+            path.push(parent);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Order in which depth first traverse yields nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfsOrder {
+    /// Node is yielded before it's descendants
+    PreOrder,
+    /// Node is yielded after all it's descendants
+    PostOrder,
+    /// Reversed post-order, so for acyclic graph
+    /// each node is yielded before nodes it has edges to
+    ReversePostOrder,
+}
+
+/// Boundary for getting iterator over depth first traverse of graph
+///
+/// Implemented for every [`Graph`]
+pub trait DfsIterable: Graph + Sized {
+    /// Depth first traverse in pre-order
+    fn dfs_iter(&self, from: usize) -> DepthFirstTraverseIterator<'_, Self> {
+        DepthFirstTraverseIterator::new(self, from, DfsOrder::PreOrder)
+    }
+
+    /// Depth first traverse in post-order
+    fn dfs_post_order_iter(&self, from: usize) -> DepthFirstTraverseIterator<'_, Self> {
+        DepthFirstTraverseIterator::new(self, from, DfsOrder::PostOrder)
+    }
+
+    /// Depth first traverse in reverse post-order
+    fn dfs_reverse_post_order_iter(&self, from: usize) -> DepthFirstTraverseIterator<'_, Self> {
+        DepthFirstTraverseIterator::new(self, from, DfsOrder::ReversePostOrder)
+    }
+
+    /// Depth first traverse from each of `sources` which is not visited yet,
+    /// reporting events to `visitor`
+    ///
+    /// Returns `Control::Break` with value of the first break
+    /// or `Control::Continue` if traverse was completed
+    ///
+    /// Computes in **O(n + e)** where n = node count, e = edge count
+    ///
+    /// **Panics** if some of sources not exists
+    fn dfs_visit<I, V>(&self, sources: I, visitor: &mut V) -> Control<V::Break>
+    where
+        I: IntoIterator<Item = usize>,
+        V: Visitor<Self::Weight>,
+    {
+        depth_first_visit(self, sources, visitor)
+    }
+}
+
+impl<G> DfsIterable for G where G: Graph {}
+
+/// Iterates over depth first traverse of graph
+/// in given [`DfsOrder`]
+///
+/// Neighbors are visited in order they are returned by graph
+///
+/// Takes **O(n)** space and computes in **O(n + e)**
+/// where n = node count, e = edge count.
+/// Reverse post-order traverses whole reachable part on first call
+pub struct DepthFirstTraverseIterator<'a, G>
+where
+    G: Graph,
+{
+    graph: &'a G,
+    order: DfsOrder,
+    start: Option<usize>,
+    visited: HashSet<usize>,
+    stack: Vec<(usize, vec::IntoIter<usize>)>,
+    finished: Option<Vec<usize>>,
+}
+
+/// Step of depth first traverse
+enum DfsStep {
+    Discovered(usize),
+    Finished(usize),
+}
+
+impl<'a, G> DepthFirstTraverseIterator<'a, G>
+where
+    G: Graph,
+{
+    pub fn new(graph: &'a G, from: usize, order: DfsOrder) -> Self {
+        Self {
+            graph,
+            order,
+            start: Some(from),
+            visited: HashSet::new(),
+            stack: Vec::new(),
+            finished: None,
+        }
+    }
+
+    fn step(&mut self) -> Option<DfsStep> {
+        if let Some(start) = self.start.take() {
+            self.enter(start);
+            return Some(DfsStep::Discovered(start));
+        }
+
+        loop {
+            let (node, neighbors) = self.stack.last_mut()?;
+
+            match neighbors.next() {
+                Some(next) if !self.visited.contains(&next) => {
+                    self.enter(next);
+                    return Some(DfsStep::Discovered(next));
+                }
+                Some(_) => continue,
+                None => {
+                    let node = *node;
+                    self.stack.pop();
+                    return Some(DfsStep::Finished(node));
+                }
+            }
+        }
+    }
+
+    fn enter(&mut self, node: usize) {
+        self.visited.insert(node);
+        let neighbors = self.graph.neighbors(node).map(|(idx, _)| idx);
+        self.stack
+            .push((node, neighbors.collect::<Vec<_>>().into_iter()));
+    }
+
+    fn next_index(&mut self) -> Option<usize> {
+        match self.order {
+            DfsOrder::PreOrder => loop {
+                if let DfsStep::Discovered(node) = self.step()? {
+                    return Some(node);
+                }
+            },
+            DfsOrder::PostOrder => loop {
+                if let DfsStep::Finished(node) = self.step()? {
+                    return Some(node);
+                }
+            },
+            DfsOrder::ReversePostOrder => {
+                if self.finished.is_none() {
+                    let mut finished = Vec::new();
+                    while let Some(step) = self.step() {
+                        if let DfsStep::Finished(node) = step {
+                            finished.push(node);
+                        }
+                    }
+                    self.finished = Some(finished);
+                }
+
+                self.finished.as_mut()?.pop()
+            }
+        }
+    }
+}
+
+impl<'a, G> Iterator for DepthFirstTraverseIterator<'a, G>
+where
+    G: Graph,
+{
+    type Item = GraphEntry<'a, G::Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cur = self.next_index()?;
+
+        Some(GraphEntry {
+            node: self.graph.get_node_by_index(cur).unwrap(),
+            edges: self.graph.neighbors(cur).map(|(_idx, node)| node).collect(),
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Control flow returned by [`Visitor`] callbacks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control<B> {
+    /// Continue traverse
+    Continue,
+    /// Don't go further from current node or edge
+    Prune,
+    /// Stop traverse and return value
+    Break(B),
+}
+
+impl<B> Control<B> {
+    /// Returns value of break or None
+    pub fn break_value(self) -> Option<B> {
+        match self {
+            Control::Break(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Callbacks of event driven depth first traverse
+///
+/// Every callback continues traverse by default,
+/// so visitor implements only events it's interested in
+pub trait Visitor<T> {
+    type Break;
+
+    /// Node is visited for the first time,
+    /// `Prune` skips all it's outgoing edges
+    fn discover_node(&mut self, _node: usize) -> Control<Self::Break> {
+        Control::Continue
+    }
+
+    /// Outgoing edge of current node is examined,
+    /// `Prune` skips the edge
+    fn examine_edge(&mut self, _edge: EdgeRef<'_, T>) -> Control<Self::Break> {
+        Control::Continue
+    }
+
+    /// Edge leads to undiscovered node,
+    /// `Prune` leaves target node undiscovered
+    fn tree_edge(&mut self, _edge: EdgeRef<'_, T>) -> Control<Self::Break> {
+        Control::Continue
+    }
+
+    /// Edge leads to node which is still being traversed, so closes cycle
+    fn back_edge(&mut self, _edge: EdgeRef<'_, T>) -> Control<Self::Break> {
+        Control::Continue
+    }
+
+    /// Edge leads to already finished node
+    fn cross_forward_edge(&mut self, _edge: EdgeRef<'_, T>) -> Control<Self::Break> {
+        Control::Continue
+    }
+
+    /// All outgoing edges of node are traversed,
+    /// `Prune` is the same as `Continue`
+    fn finish_node(&mut self, _node: usize) -> Control<Self::Break> {
+        Control::Continue
+    }
+}
+
+/// Returns from function on `Control::Break`,
+/// otherwise evaluates to given control
+macro_rules! try_control {
+    ($control:expr) => {
+        match $control {
+            Control::Break(value) => return Control::Break(value),
+            control => control,
+        }
+    };
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Undiscovered,
+    InProgress,
+    Finished,
+}
+
+fn depth_first_visit<G, I, V>(graph: &G, sources: I, visitor: &mut V) -> Control<V::Break>
+where
+    G: Graph,
+    I: IntoIterator<Item = usize>,
+    V: Visitor<G::Weight>,
+{
+    let mut colors = vec![Color::Undiscovered; graph.node_bound()];
+    let mut stack = Vec::new();
+
+    for source in sources {
+        if graph.get_node_by_index(source).is_none() {
+            panic!("Node with index {} not found", source);
+        }
+
+        if colors[source] != Color::Undiscovered {
+            continue;
+        }
+
+        colors[source] = Color::InProgress;
+        match try_control!(visitor.discover_node(source)) {
+            Control::Continue => stack.push((source, graph.edges_from(source))),
+            _ => {
+                colors[source] = Color::Finished;
+                try_control!(visitor.finish_node(source));
+            }
+        }
+
+        while let Some((node, edges)) = stack.last_mut() {
+            let edge = match edges.next() {
+                Some(edge) => edge,
+                None => {
+                    let node = *node;
+                    stack.pop();
+                    colors[node] = Color::Finished;
+                    try_control!(visitor.finish_node(node));
+                    continue;
+                }
+            };
+
+            if let Control::Prune = try_control!(visitor.examine_edge(edge)) {
+                continue;
+            }
+
+            match colors[edge.to] {
+                Color::Undiscovered => {
+                    if let Control::Prune = try_control!(visitor.tree_edge(edge)) {
+                        continue;
+                    }
+
+                    colors[edge.to] = Color::InProgress;
+                    match try_control!(visitor.discover_node(edge.to)) {
+                        Control::Continue => stack.push((edge.to, graph.edges_from(edge.to))),
+                        _ => {
+                            colors[edge.to] = Color::Finished;
+                            try_control!(visitor.finish_node(edge.to));
+                        }
+                    }
+                }
+                Color::InProgress => {
+                    try_control!(visitor.back_edge(edge));
+                }
+                Color::Finished => {
+                    try_control!(visitor.cross_forward_edge(edge));
+                }
+            }
+        }
+    }
+
+    Control::Continue
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::csr_graph::CsrGraph;
+    use crate::list_graph::ListGraph;
     use crate::matrix_graph::MatrixGraph;
+    use crate::types::{GetEdgeByIndex, Reversed};
 
     fn create_graph() -> MatrixGraph<u8, ()> {
         MatrixGraph::<u8, ()>::default()
@@ -96,19 +679,13 @@ mod tests {
     fn collects_all_graph() {
         let mut g = create_graph();
 
-        let expected = vec![(1, vec![4, 2, 3]), (4, vec![1]), (2, vec![3]), (3, vec![])];
+        let expected = [(1, vec![4, 2, 3]), (4, vec![1]), (2, vec![3]), (3, vec![])];
 
         for (info, edges) in expected.iter() {
             let info = *info;
-            let from_idx = match g.contains_node(&info) {
-                true => g.get_index_of(&info).unwrap(),
-                false => g.add_node(info),
-            };
+            let from_idx = g.node_entry(info).or_insert();
             for edge in edges {
-                let to_idx = match g.contains_node(edge) {
-                    true => g.get_index_of(edge).unwrap(),
-                    false => g.add_node(*edge),
-                };
+                let to_idx = g.node_entry(*edge).or_insert();
                 g.add_edge(from_idx, to_idx, ());
             }
         }
@@ -125,4 +702,354 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn traverses_against_edge_direction() {
+        let g = MatrixGraph::<u8, ()>::from_edges([(1, 3, ()), (2, 3, ()), (3, 4, ()), (5, 1, ())]);
+        let reversed = Reversed(&g);
+
+        let actual = BreadthFirstTraverseIterator::new(&reversed, 3)
+            .map(|entry| *entry.node)
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, vec![4, 3, 1, 2, 5]);
+    }
+
+    /// Algorithm written once against `Graph`
+    fn total_weight<G>(g: &G) -> u32
+    where
+        G: Graph<Weight = u32>,
+    {
+        g.node_indices()
+            .flat_map(|node| g.edges_from(node))
+            .map(|edge| *edge.weight)
+            .sum()
+    }
+
+    #[test]
+    fn runs_generic_algorithm_on_any_graph() {
+        let edges = [(1, 2, 3), (2, 3, 4), (3, 1, 5), (1, 3, 6)];
+
+        let mut matrix = MatrixGraph::<u8, u32>::from_edges(edges);
+        let list = ListGraph::<u8, u32>::from_edges(edges);
+        let csr = CsrGraph::<u8, u32>::from_edges(edges);
+
+        assert_eq!(total_weight(&matrix), 18);
+        assert_eq!(total_weight(&list), 18);
+        assert_eq!(total_weight(&csr), 18);
+        assert_eq!(total_weight(&Reversed(&matrix)), 18);
+
+        matrix.remove_node(1);
+        assert_eq!(matrix.node_indices().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(total_weight(&matrix), 11);
+    }
+
+    #[test]
+    fn reversed_view_swaps_edge_endpoints() {
+        let g = MatrixGraph::<u8, u32>::from_edges([(1, 2, 3), (3, 2, 4)]);
+        let reversed = Reversed(&g);
+
+        let actual = reversed
+            .edges_from(1)
+            .map(|edge| (edge.from, edge.to, *edge.weight))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![(1, 0, 3), (1, 2, 4)]);
+        assert_eq!(reversed.get_edge_by_index(1, 0), Some(&3));
+        assert_eq!(reversed.get_edge_by_index(0, 1), None);
+    }
+
+    fn create_diamond() -> MatrixGraph<u8, ()> {
+        MatrixGraph::<u8, ()>::from_edges([
+            (1, 2, ()),
+            (1, 3, ()),
+            (2, 4, ()),
+            (3, 4, ()),
+            (4, 1, ()),
+        ])
+    }
+
+    #[test]
+    fn traverses_depth_first_in_pre_order() {
+        let g = create_diamond();
+
+        let actual = g.dfs_iter(0).collect::<Vec<_>>();
+        let expected = vec![
+            GraphEntry {
+                node: &1,
+                edges: vec![&2, &3],
+            },
+            GraphEntry {
+                node: &2,
+                edges: vec![&4],
+            },
+            GraphEntry {
+                node: &4,
+                edges: vec![&1],
+            },
+            GraphEntry {
+                node: &3,
+                edges: vec![&4],
+            },
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn traverses_depth_first_in_post_order() {
+        let g = create_diamond();
+        let actual = g
+            .dfs_post_order_iter(0)
+            .map(|entry| *entry.node)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![4, 2, 3, 1]);
+    }
+
+    #[test]
+    fn traverses_depth_first_in_reverse_post_order() {
+        let g = MatrixGraph::<u8, ()>::from_edges([(1, 2, ()), (1, 3, ()), (3, 2, ()), (2, 4, ())]);
+        let actual = g
+            .dfs_reverse_post_order_iter(0)
+            .map(|entry| *entry.node)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![1, 3, 2, 4]);
+    }
+
+    #[test]
+    fn depth_first_traverse_visits_only_reachable_nodes() {
+        let g = create_diamond();
+        let actual = g.dfs_iter(3).map(|entry| *entry.node).collect::<Vec<_>>();
+        assert_eq!(actual, vec![4, 1, 2, 3]);
+
+        let g = MatrixGraph::<u8, ()>::from_edges([(1, 2, ()), (3, 4, ())]);
+        assert_eq!(g.dfs_post_order_iter(0).count(), 2);
+        assert_eq!(g.dfs_reverse_post_order_iter(2).count(), 2);
+    }
+
+    #[test]
+    fn reports_depth_and_parent() {
+        let g = create_diamond();
+
+        let actual = g
+            .bfs_tree_iter(0)
+            .map(|entry| (*entry.node, entry.depth, entry.parent))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            vec![
+                (1, 0, None),
+                (2, 1, Some(0)),
+                (3, 1, Some(0)),
+                (4, 2, Some(1))
+            ]
+        );
+    }
+
+    #[test]
+    fn stops_at_max_depth() {
+        let g = create_diamond();
+
+        let actual = g
+            .bfs_tree_iter(0)
+            .max_depth(1)
+            .map(|entry| *entry.node)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![1, 2, 3]);
+        assert_eq!(g.bfs_tree_iter(0).max_depth(0).count(), 1);
+    }
+
+    #[test]
+    fn collects_distances_and_paths() {
+        let mut g =
+            MatrixGraph::<u8, ()>::from_edges([(1, 2, ()), (2, 3, ()), (3, 4, ()), (1, 3, ())]);
+        let isolated = g.add_node(5);
+
+        let tree = g.bfs_tree(0);
+        assert_eq!(tree.from(), 0);
+        assert_eq!(
+            tree.distances(),
+            &[Some(0), Some(1), Some(1), Some(2), None]
+        );
+        assert_eq!(tree.parent(3), Some(2));
+        assert_eq!(tree.path_to(3), Some(vec![0, 2, 3]));
+        assert_eq!(tree.path_to(0), Some(vec![0]));
+        assert_eq!(tree.path_to(isolated), None);
+        assert_eq!(tree.distance(10), None);
+    }
+
+    #[test]
+    fn traverses_from_multiple_sources() {
+        let g = MatrixGraph::<u8, ()>::from_edges([(1, 2, ()), (3, 4, ()), (4, 5, ()), (2, 5, ())]);
+
+        let actual = g
+            .bfs_iter_from([0, 2, 0])
+            .map(|entry| *entry.node)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![1, 3, 2, 4, 5]);
+
+        let depths = g
+            .bfs_tree_iter_from([0, 2])
+            .map(|entry| (*entry.node, entry.depth))
+            .collect::<Vec<_>>();
+        assert_eq!(depths, vec![(1, 0), (3, 0), (2, 1), (4, 1), (5, 2)]);
+    }
+
+    #[test]
+    fn traverses_whole_graph_with_disconnected_components() {
+        let mut g = MatrixGraph::<u8, ()>::from_edges([(1, 2, ()), (3, 4, ()), (5, 3, ())]);
+        let isolated = g.add_node(6);
+        g.remove_node(0);
+
+        let actual = g
+            .bfs_iter_all()
+            .map(|entry| *entry.node)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![2, 3, 4, 5, 6]);
+
+        let roots = BreadthFirstTreeIterator::whole_graph(&g)
+            .filter(|entry| entry.parent.is_none())
+            .map(|entry| entry.index)
+            .collect::<Vec<_>>();
+        assert_eq!(roots, vec![1, 2, 4, isolated]);
+    }
+
+    /// Records all events in order
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+        prune_at: Option<usize>,
+    }
+
+    impl Visitor<()> for Recorder {
+        type Break = ();
+
+        fn discover_node(&mut self, node: usize) -> Control<()> {
+            self.events.push(format!("discover {}", node));
+            match self.prune_at == Some(node) {
+                true => Control::Prune,
+                false => Control::Continue,
+            }
+        }
+
+        fn tree_edge(&mut self, edge: EdgeRef<'_, ()>) -> Control<()> {
+            self.events.push(format!("tree {}->{}", edge.from, edge.to));
+            Control::Continue
+        }
+
+        fn back_edge(&mut self, edge: EdgeRef<'_, ()>) -> Control<()> {
+            self.events.push(format!("back {}->{}", edge.from, edge.to));
+            Control::Continue
+        }
+
+        fn cross_forward_edge(&mut self, edge: EdgeRef<'_, ()>) -> Control<()> {
+            self.events
+                .push(format!("cross {}->{}", edge.from, edge.to));
+            Control::Continue
+        }
+
+        fn finish_node(&mut self, node: usize) -> Control<()> {
+            self.events.push(format!("finish {}", node));
+            Control::Continue
+        }
+    }
+
+    #[test]
+    fn visitor_receives_events_in_order() {
+        let g = create_diamond();
+        let mut recorder = Recorder::default();
+
+        assert_eq!(g.dfs_visit([0], &mut recorder), Control::Continue);
+        assert_eq!(
+            recorder.events,
+            vec![
+                "discover 0",
+                "tree 0->1",
+                "discover 1",
+                "tree 1->3",
+                "discover 3",
+                "back 3->0",
+                "finish 3",
+                "finish 1",
+                "tree 0->2",
+                "discover 2",
+                "cross 2->3",
+                "finish 2",
+                "finish 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn visitor_prunes_subtree() {
+        let g = create_diamond();
+        let mut recorder = Recorder {
+            prune_at: Some(1),
+            ..Default::default()
+        };
+
+        g.dfs_visit([0], &mut recorder);
+        assert!(recorder.events.contains(&"finish 1".to_string()));
+        assert!(!recorder.events.contains(&"tree 1->3".to_string()));
+        assert!(recorder.events.contains(&"tree 2->3".to_string()));
+    }
+
+    /// Stops on the first edge closing cycle
+    struct CycleDetector;
+
+    impl Visitor<()> for CycleDetector {
+        type Break = (usize, usize);
+
+        fn back_edge(&mut self, edge: EdgeRef<'_, ()>) -> Control<(usize, usize)> {
+            Control::Break((edge.from, edge.to))
+        }
+    }
+
+    #[test]
+    fn visitor_detects_cycle() {
+        let g = create_diamond();
+        let all = g.node_indices().collect::<Vec<_>>();
+        assert_eq!(g.dfs_visit(all, &mut CycleDetector), Control::Break((3, 0)));
+
+        let g = MatrixGraph::<u8, ()>::from_edges([(1, 2, ()), (1, 3, ()), (2, 3, ())]);
+        let all = g.node_indices().collect::<Vec<_>>();
+        assert_eq!(g.dfs_visit(all, &mut CycleDetector).break_value(), None);
+    }
+
+    /// Searches node with given index skipping heavy edges
+    struct Search {
+        target: usize,
+        max_weight: u32,
+    }
+
+    impl Visitor<u32> for Search {
+        type Break = usize;
+
+        fn examine_edge(&mut self, edge: EdgeRef<'_, u32>) -> Control<usize> {
+            match *edge.weight > self.max_weight {
+                true => Control::Prune,
+                false => Control::Continue,
+            }
+        }
+
+        fn discover_node(&mut self, node: usize) -> Control<usize> {
+            match node == self.target {
+                true => Control::Break(node),
+                false => Control::Continue,
+            }
+        }
+    }
+
+    #[test]
+    fn visitor_searches_with_pruned_edges() {
+        let g = MatrixGraph::<u8, u32>::from_edges([(1, 2, 5), (1, 3, 1), (3, 4, 1), (2, 5, 1)]);
+
+        let mut search = Search {
+            target: 3,
+            max_weight: 2,
+        };
+        assert_eq!(g.dfs_visit([0], &mut search), Control::Break(3));
+
+        search.target = 4;
+        assert_eq!(g.dfs_visit([0], &mut search), Control::Continue);
+    }
 }
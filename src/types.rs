@@ -1,12 +1,42 @@
-use crate::node_storage::NodeStorage;
+use crate::matrix_graph::{MatrixGraphEdgesIterator, MatrixGraphNeighborsIterator};
+use crate::node_storage::{DefaultBuildHasher, NodeStorage};
 use crate::serialization;
 use std::fmt;
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash};
 
 /// Base type for graph node
 pub trait MatrixGraphNode: Eq + Hash {}
 impl<N> MatrixGraphNode for N where N: Eq + Hash {}
 
+/// Node handle which detects reuse of removed node's index
+///
+/// Plain `usize` index of removed node can be given to another
+/// node later, while handle stays bound to generation of node
+/// it was created for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeIndex {
+    index: usize,
+    generation: usize,
+}
+
+impl NodeIndex {
+    pub(crate) fn new(index: usize, generation: usize) -> Self {
+        Self { index, generation }
+    }
+
+    /// Returns raw index of node
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns generation of node's slot
+    #[inline]
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+}
+
 /// Structure for returning while traversing graph
 #[derive(Debug, PartialEq, Eq)]
 pub struct GraphEntry<'a, N>
@@ -17,76 +47,361 @@ where
     pub edges: Vec<&'a N>,
 }
 
-/// Boundary for getting neighbors by graph node index
-pub trait Neighbors<'a, N: 'a, I>
+/// Structure for returning while traversing graph
+/// breadth first with tracking of traverse tree
+#[derive(Debug, PartialEq, Eq)]
+pub struct BfsEntry<'a, N>
+where
+    N: MatrixGraphNode,
+{
+    pub index: usize,
+    pub node: &'a N,
+    /// Count of edges on the shortest path from start node
+    pub depth: usize,
+    /// Node from which this one was discovered, None for start node
+    pub parent: Option<usize>,
+    pub edges: Vec<&'a N>,
+}
+
+/// Common interface of graph representations
+///
+/// Algorithms written against it run on any graph.
+/// Nodes are addressed by indices below [`NodeBound::node_bound`],
+/// some of which may belong to removed nodes
+pub trait Graph:
+    GetNodeByIndex<<Self as Graph>::Node> + GetEdgeByIndex<<Self as Graph>::Weight> + NodeBound
+{
+    type Node: MatrixGraphNode;
+    type Weight;
+
+    /// Iterator over successors of node
+    type Neighbors<'a>: Iterator<Item = (usize, &'a Self::Node)>
+    where
+        Self: 'a;
+
+    /// Iterator over outgoing edges of node
+    type Edges<'a>: Iterator<Item = EdgeRef<'a, Self::Weight>>
+    where
+        Self: 'a;
+
+    /// Returns count of nodes
+    fn node_count(&self) -> usize;
+
+    /// Returns count of edges
+    fn edge_count(&self) -> usize;
+
+    /// Returns successors of node
+    ///
+    /// **Panics** if node not exists
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_>;
+
+    /// Returns outgoing edges of node,
+    /// which are empty if node not exists
+    fn edges_from(&self, node: usize) -> Self::Edges<'_>;
+
+    /// Returns false if every edge is yielded by `edges_from`
+    /// of both it's ends, so it should be counted once
+    #[inline]
+    fn is_directed(&self) -> bool {
+        true
+    }
+
+    /// Returns indices of existing nodes in ascending order
+    fn node_indices(&self) -> NodeIndices<'_, Self>
+    where
+        Self: Sized,
+    {
+        NodeIndices {
+            graph: self,
+            next: 0,
+        }
+    }
+}
+
+/// Iterates over indices of existing nodes of graph
+pub struct NodeIndices<'a, G> {
+    graph: &'a G,
+    next: usize,
+}
+
+impl<'a, G> Iterator for NodeIndices<'a, G>
 where
-    I: Iterator<Item = (usize, &'a N)>,
+    G: Graph,
 {
-    fn neighbors(&'a self, node: usize) -> IteratorHandle<'a, N, I>;
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.graph.node_bound() {
+            self.next += 1;
+            if self.graph.get_node_by_index(self.next - 1).is_some() {
+                return Some(self.next - 1);
+            }
+        }
+        None
+    }
+}
+
+/// Boundary for getting incoming neighbors by graph node index,
+/// so nodes which have edge to given one
+pub trait IncomingNeighbors: Graph {
+    /// Iterator over predecessors of node
+    type Predecessors<'a>: Iterator<Item = (usize, &'a Self::Node)>
+    where
+        Self: 'a;
+
+    /// Iterator over incoming edges of node
+    type IncomingEdges<'a>: Iterator<Item = EdgeRef<'a, Self::Weight>>
+    where
+        Self: 'a;
+
+    /// Returns predecessors of node
+    ///
+    /// **Panics** if node not exists
+    fn predecessors(&self, node: usize) -> Self::Predecessors<'_>;
+
+    /// Returns incoming edges of node,
+    /// which are empty if node not exists
+    fn edges_to(&self, node: usize) -> Self::IncomingEdges<'_>;
 }
 
-/// Generic iterator wrapper
-pub struct IteratorHandle<'a, N: 'a, I>
+/// View of graph with reversed edges
+///
+/// Neighbors of node in view are predecessors of node in graph,
+/// so traversals over view run against edge direction
+pub struct Reversed<'a, G>(pub &'a G);
+
+impl<'a, G> GetNodeByIndex<G::Node> for Reversed<'a, G>
 where
-    I: Iterator<Item = (usize, &'a N)>,
+    G: IncomingNeighbors,
 {
-    pub iterator: I,
+    #[inline]
+    fn get_node_by_index(&self, node_idx: usize) -> Option<&G::Node> {
+        self.0.get_node_by_index(node_idx)
+    }
+}
+
+impl<'a, G> GetEdgeByIndex<G::Weight> for Reversed<'a, G>
+where
+    G: IncomingNeighbors,
+{
+    #[inline]
+    fn get_edge_by_index(&self, from: usize, to: usize) -> Option<&G::Weight> {
+        self.0.get_edge_by_index(to, from)
+    }
 }
 
-impl<'a, N: 'a, I> IteratorHandle<'a, N, I>
+impl<'a, G> NodeBound for Reversed<'a, G>
 where
-    I: Iterator<Item = (usize, &'a N)>,
+    G: IncomingNeighbors,
 {
-    pub fn new(iterator: I) -> Self {
-        Self { iterator }
+    #[inline]
+    fn node_bound(&self) -> usize {
+        self.0.node_bound()
+    }
+}
+
+impl<'a, G> Graph for Reversed<'a, G>
+where
+    G: IncomingNeighbors,
+{
+    type Node = G::Node;
+    type Weight = G::Weight;
+    type Neighbors<'b>
+        = G::Predecessors<'b>
+    where
+        Self: 'b;
+    type Edges<'b>
+        = ReversedEdges<G::IncomingEdges<'b>>
+    where
+        Self: 'b;
+
+    #[inline]
+    fn node_count(&self) -> usize {
+        self.0.node_count()
+    }
+
+    #[inline]
+    fn edge_count(&self) -> usize {
+        self.0.edge_count()
+    }
+
+    #[inline]
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        self.0.predecessors(node)
+    }
+
+    #[inline]
+    fn edges_from(&self, node: usize) -> Self::Edges<'_> {
+        ReversedEdges(self.0.edges_to(node))
     }
 }
 
-impl<'a, N: 'a, I> Iterator for IteratorHandle<'a, N, I>
+/// Iterates over edges swapping their endpoints
+pub struct ReversedEdges<I>(I);
+
+impl<'a, T: 'a, I> Iterator for ReversedEdges<I>
 where
-    I: Iterator<Item = (usize, &'a N)>,
+    I: Iterator<Item = EdgeRef<'a, T>>,
 {
-    type Item = (usize, &'a N);
+    type Item = EdgeRef<'a, T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iterator.next()
+        let edge = self.0.next()?;
+        Some(EdgeRef {
+            from: edge.to,
+            to: edge.from,
+            weight: edge.weight,
+        })
     }
 }
 
 /// Boundary for getting nodes by index
-/// Can be changed to defalt Index after stable GAT
 pub trait GetNodeByIndex<N> {
     fn get_node_by_index(&self, node_idx: usize) -> Option<&N>;
 }
 
 /// Boundary for getting edges by index
-/// Can be changed to defalt Index after stable GAT
 pub trait GetEdgeByIndex<T> {
     fn get_edge_by_index(&self, from: usize, to: usize) -> Option<&T>;
 }
 
+/// Edge of graph with it's endpoints and weight
+#[derive(Debug, PartialEq, Eq)]
+pub struct EdgeRef<'a, T> {
+    pub from: usize,
+    pub to: usize,
+    pub weight: &'a T,
+}
+
+impl<'a, T> Clone for EdgeRef<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for EdgeRef<'a, T> {}
+
+/// Boundary for getting upper bound of node indices
+///
+/// All indices of existing nodes are less than bound,
+/// but some indices below it may belong to removed nodes
+pub trait NodeBound {
+    fn node_bound(&self) -> usize;
+}
+
 /// Boundary for representing graph as adjacency matrix
-pub trait Adjacency<N, T>
+pub trait Adjacency<N, T, S = DefaultBuildHasher>
 where
     N: MatrixGraphNode,
 {
-    fn get_adjacency_matrix(&self) -> AdjacencyMatrix<N, T>;
+    fn get_adjacency_matrix(&self) -> AdjacencyMatrix<'_, N, T, S>;
 }
 
 /// Structure for representing graph as adjacency matrix
 #[derive(Debug, PartialEq, Eq)]
-pub struct AdjacencyMatrix<'a, N, T>
+pub struct AdjacencyMatrix<'a, N, T, S = DefaultBuildHasher>
+where
+    N: MatrixGraphNode,
+{
+    pub nodes: &'a NodeStorage<N, S>,
+    /// Matrix cells in row-major order
+    pub edges: &'a [Option<T>],
+    /// Length of matrix row
+    pub stride: usize,
+}
+
+impl<'a, N, T, S> AdjacencyMatrix<'a, N, T, S>
+where
+    N: MatrixGraphNode,
+{
+    /// Returns cells with outgoing edges of node
+    /// or empty slice if node is out of matrix
+    pub fn row(&self, node: usize) -> &'a [Option<T>] {
+        if node >= self.stride {
+            return &[];
+        }
+        &self.edges[node * self.stride..(node + 1) * self.stride]
+    }
+}
+
+impl<'a, N, T, S> GetNodeByIndex<N> for AdjacencyMatrix<'a, N, T, S>
 where
     N: MatrixGraphNode,
+    S: BuildHasher,
 {
-    pub nodes: &'a NodeStorage<N>,
-    pub edges: &'a Vec<Vec<Option<T>>>,
+    #[inline]
+    fn get_node_by_index(&self, node_idx: usize) -> Option<&N> {
+        self.nodes.get_node_by_index(node_idx)
+    }
+}
+
+impl<'a, N, T, S> GetEdgeByIndex<T> for AdjacencyMatrix<'a, N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    #[inline]
+    fn get_edge_by_index(&self, from: usize, to: usize) -> Option<&T> {
+        self.row(from).get(to)?.as_ref()
+    }
+}
+
+impl<'a, N, T, S> NodeBound for AdjacencyMatrix<'a, N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    #[inline]
+    fn node_bound(&self) -> usize {
+        self.nodes.bound()
+    }
+}
+
+impl<'a, N, T, S> Graph for AdjacencyMatrix<'a, N, T, S>
+where
+    N: MatrixGraphNode,
+    S: BuildHasher,
+{
+    type Node = N;
+    type Weight = T;
+    type Neighbors<'b>
+        = MatrixGraphNeighborsIterator<'b, N, T, S>
+    where
+        Self: 'b;
+    type Edges<'b>
+        = MatrixGraphEdgesIterator<'b, T>
+    where
+        Self: 'b;
+
+    #[inline]
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Computes in **O(c ^ 2)** where c = row length
+    fn edge_count(&self) -> usize {
+        self.edges.iter().filter(|edge| edge.is_some()).count()
+    }
+
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        if self.nodes.get_node_by_index(node).is_none() {
+            panic!("Node with index {} not found", node);
+        }
+
+        MatrixGraphNeighborsIterator::new(self.nodes, self.row(node))
+    }
+
+    #[inline]
+    fn edges_from(&self, node: usize) -> Self::Edges<'_> {
+        MatrixGraphEdgesIterator::row(self.edges, self.stride, node)
+    }
 }
 
-impl<'a, N, T> fmt::Display for AdjacencyMatrix<'a, N, T>
+impl<'a, N, T, S> fmt::Display for AdjacencyMatrix<'a, N, T, S>
 where
     N: fmt::Display + MatrixGraphNode,
     T: fmt::Display,
+    S: BuildHasher,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", serialization::ser_tgf(self))?;
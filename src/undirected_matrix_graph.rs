@@ -0,0 +1,474 @@
+use crate::matrix_graph::NodeIterator;
+use crate::node_storage::NodeStorage;
+use crate::types::{EdgeRef, GetEdgeByIndex, GetNodeByIndex, Graph, MatrixGraphNode, NodeBound};
+use std::cmp;
+
+/// Undirected graph representation with adjacency matrix
+///
+/// Edges are stored once in lower triangular matrix,
+/// so edge `(a, b)` is the same as edge `(b, a)`
+///
+/// Note: it's better to use for dense graph
+pub struct UndirectedMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    nodes: NodeStorage<N>,
+    adjacency: Vec<Option<T>>,
+    edge_count: usize,
+}
+
+impl<N, T> Default for UndirectedMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    fn default() -> Self {
+        Self {
+            nodes: NodeStorage::default(),
+            adjacency: Vec::new(),
+            edge_count: 0,
+        }
+    }
+}
+
+impl<N, T> UndirectedMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    /// Create UndirectedMatrixGraph from iterator of tuples
+    /// where each element representes edge between
+    /// two nodes and it's weight
+    pub fn from_edges(edges: impl IntoIterator<Item = (N, N, T)>) -> Self {
+        let mut g = Self::default();
+
+        for (from, to, weight) in edges {
+            let from_idx = g.nodes.get_or_insert(from);
+            let to_idx = g.nodes.get_or_insert(to);
+            g.add_edge(from_idx, to_idx, weight);
+        }
+
+        g
+    }
+}
+
+impl<N, T> UndirectedMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    /// Adds new node in graph
+    ///
+    /// Returns index of new node
+    ///
+    /// Computes in **O(1)** (average amortized)
+    /// Worse case **O(n)** where n is nodes count
+    ///
+    /// **Panics** if node already exists
+    pub fn add_node(&mut self, node: N) -> usize {
+        self.nodes.add(node)
+    }
+
+    /// Removes node and all edges for it
+    ///
+    /// Returns removed node or None, if node not found
+    ///
+    /// Computes in **O(n)** where n = nodes count
+    pub fn remove_node(&mut self, node_index: usize) -> Option<N> {
+        self.nodes.get_node_by_index(node_index)?;
+
+        for i in 0..self.nodes.bound() {
+            self.remove_edge(node_index, i);
+        }
+
+        self.nodes.remove(node_index)
+    }
+
+    /// Adds edge between two nodes
+    ///
+    /// Computes in **O(1)** (average)
+    /// Worst case **O(n ^ 2)** where n = nodes count
+    ///
+    /// **Panics** if some of nodes not exists or edge already exists
+    pub fn add_edge(&mut self, a: usize, b: usize, weight: T) {
+        let missing = [a, b]
+            .into_iter()
+            .filter(|idx| self.nodes.get_node_by_index(*idx).is_none())
+            .max();
+
+        if let Some(idx) = missing {
+            panic!("Can't add edge for not existing node with index {}", idx);
+        }
+
+        if self.update_edge(a, b, weight).is_some() {
+            panic!("Edge from {} to {} already exists", a, b);
+        }
+    }
+
+    /// Removes edge between two nodes in any order
+    ///
+    /// Returns edge's weight if removed else None
+    ///
+    /// Computes in **O(1)**
+    pub fn remove_edge(&mut self, a: usize, b: usize) -> Option<T> {
+        let old_edge = self.adjacency.get_mut(Self::position(a, b))?.take();
+
+        if old_edge.is_some() {
+            self.edge_count -= 1;
+        }
+
+        old_edge
+    }
+
+    /// Returns count of nodes
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns count of edges, each undirected edge is counted once
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Returns upper bound of node indices
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn node_bound(&self) -> usize {
+        self.nodes.bound()
+    }
+
+    /// Returns iterator over nodes
+    #[inline]
+    pub fn node_iter(&self) -> NodeIterator<'_, N> {
+        NodeIterator::new(&self.nodes)
+    }
+
+    /// Checks if node exists in graph
+    ///
+    /// Computes in **O(1)** (average)
+    #[inline]
+    pub fn contains_node(&self, node: &N) -> bool {
+        self.nodes.contains(node)
+    }
+
+    /// Checks if edge between two nodes exists in any order
+    ///
+    /// Computes in **O(1)**
+    #[inline]
+    pub fn contains_edge(&self, a: usize, b: usize) -> bool {
+        self.get_edge_by_index(a, b).is_some()
+    }
+
+    /// Returns index of node or None if not found
+    ///
+    /// Computes in **O(1)** (average)
+    #[inline]
+    pub fn get_index_of(&self, node: &N) -> Option<usize> {
+        self.nodes.get_index_of(node)
+    }
+
+    fn update_edge(&mut self, a: usize, b: usize, weight: T) -> Option<T> {
+        let position = Self::position(a, b);
+
+        if position >= self.adjacency.len() {
+            self.adjacency.resize_with(position + 1, || None);
+        }
+
+        let last_edge = self.adjacency[position].replace(weight);
+
+        if last_edge.is_none() {
+            self.edge_count += 1;
+        }
+
+        last_edge
+    }
+
+    /// Position of edge in lower triangular matrix
+    #[inline]
+    fn position(a: usize, b: usize) -> usize {
+        let (row, column) = (cmp::max(a, b), cmp::min(a, b));
+        row * (row + 1) / 2 + column
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////////////
+
+impl<N, T> GetNodeByIndex<N> for UndirectedMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    #[inline]
+    fn get_node_by_index(&self, node_idx: usize) -> Option<&N> {
+        self.nodes.get_node_by_index(node_idx)
+    }
+}
+
+impl<N, T> NodeBound for UndirectedMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    #[inline]
+    fn node_bound(&self) -> usize {
+        self.nodes.bound()
+    }
+}
+
+impl<N, T> GetEdgeByIndex<T> for UndirectedMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    #[inline]
+    fn get_edge_by_index(&self, a: usize, b: usize) -> Option<&T> {
+        self.adjacency.get(Self::position(a, b))?.as_ref()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+pub struct UndirectedMatrixGraphNeighborsIterator<'a, N, T>
+where
+    N: MatrixGraphNode,
+{
+    node: usize,
+    column: usize,
+    graph: &'a UndirectedMatrixGraph<N, T>,
+}
+
+impl<'a, N, T> Iterator for UndirectedMatrixGraphNeighborsIterator<'a, N, T>
+where
+    N: MatrixGraphNode,
+{
+    type Item = (usize, &'a N);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.column >= self.graph.nodes.bound() {
+                return None;
+            }
+
+            self.column += 1;
+            if self.graph.contains_edge(self.node, self.column - 1) {
+                return Some((
+                    self.column - 1,
+                    self.graph.nodes.get_node_by_index(self.column - 1)?,
+                ));
+            }
+        }
+    }
+}
+
+/// Iterates over edges of node, where `from` is always given node
+pub struct UndirectedMatrixGraphEdgesIterator<'a, N, T>
+where
+    N: MatrixGraphNode,
+{
+    node: usize,
+    column: usize,
+    graph: &'a UndirectedMatrixGraph<N, T>,
+}
+
+impl<'a, N, T> Iterator for UndirectedMatrixGraphEdgesIterator<'a, N, T>
+where
+    N: MatrixGraphNode,
+{
+    type Item = EdgeRef<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.column < self.graph.nodes.bound() {
+            self.column += 1;
+            if let Some(weight) = self.graph.get_edge_by_index(self.node, self.column - 1) {
+                return Some(EdgeRef {
+                    from: self.node,
+                    to: self.column - 1,
+                    weight,
+                });
+            }
+        }
+        None
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl<N, T> Graph for UndirectedMatrixGraph<N, T>
+where
+    N: MatrixGraphNode,
+{
+    type Node = N;
+    type Weight = T;
+    type Neighbors<'a>
+        = UndirectedMatrixGraphNeighborsIterator<'a, N, T>
+    where
+        Self: 'a;
+    type Edges<'a>
+        = UndirectedMatrixGraphEdgesIterator<'a, N, T>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Each undirected edge is counted once
+    #[inline]
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    #[inline]
+    fn is_directed(&self) -> bool {
+        false
+    }
+
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        if self.nodes.get_node_by_index(node).is_none() {
+            panic!("Node with index {} not found", node);
+        }
+
+        UndirectedMatrixGraphNeighborsIterator {
+            node,
+            column: 0,
+            graph: self,
+        }
+    }
+
+    /// Yields every edge of node, so each edge is yielded
+    /// from both of it's ends and sum of `edges_from` lengths
+    /// is greater than `edge_count`
+    fn edges_from(&self, node: usize) -> Self::Edges<'_> {
+        UndirectedMatrixGraphEdgesIterator {
+            node,
+            column: 0,
+            graph: self,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traversable::BfsIterable;
+    use crate::types::GraphEntry;
+
+    fn create_graph() -> UndirectedMatrixGraph<u32, u32> {
+        UndirectedMatrixGraph::<u32, u32>::default()
+    }
+
+    #[test]
+    fn test_creates_default_empty() {
+        let g = create_graph();
+        assert_eq!(g.node_count(), 0);
+        assert_eq!(g.edge_count(), 0);
+    }
+
+    #[test]
+    fn test_edge_is_order_independent() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        g.add_edge(b, a, 7);
+
+        assert_eq!(g.edge_count(), 1);
+        assert!(g.contains_edge(a, b));
+        assert!(g.contains_edge(b, a));
+        assert_eq!(g.get_edge_by_index(a, b), Some(&7));
+        assert_eq!(g.get_edge_by_index(b, a), Some(&7));
+    }
+
+    #[test]
+    #[should_panic(expected = "Edge from 0 to 1 already exists")]
+    fn test_panics_on_creating_flipped_existing_edge() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        g.add_edge(b, a, 1);
+        g.add_edge(a, b, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't add edge for not existing node with index 1")]
+    fn test_panics_on_create_edge_for_not_existing_node() {
+        let mut g = create_graph();
+        g.add_edge(0, 1, 1);
+    }
+
+    #[test]
+    fn test_removes_flipped_edge() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        g.add_edge(a, b, 3);
+
+        assert_eq!(g.remove_edge(b, a), Some(3));
+        assert_eq!(g.remove_edge(a, b), None);
+        assert_eq!(g.edge_count(), 0);
+    }
+
+    #[test]
+    fn test_counts_self_loop_once() {
+        let mut g = create_graph();
+        let a = g.add_node(1);
+        g.add_edge(a, a, 3);
+        assert_eq!(g.edge_count(), 1);
+        assert_eq!(g.neighbors(a).count(), 1);
+    }
+
+    #[test]
+    fn test_removes_node_with_edges() {
+        let mut g =
+            UndirectedMatrixGraph::<u32, u32>::from_edges([(1, 2, 1), (2, 3, 2), (3, 1, 3)]);
+
+        assert_eq!(g.remove_node(1), Some(2));
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.edge_count(), 1);
+        assert!(g.contains_edge(0, 2));
+    }
+
+    #[test]
+    fn test_neighbors_include_both_directions() {
+        let g = UndirectedMatrixGraph::<u32, u32>::from_edges([(1, 2, 1), (3, 1, 2)]);
+        let actual = g.neighbors(0).map(|(_, node)| *node).collect::<Vec<_>>();
+        assert_eq!(actual, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_edges_from_start_at_given_node() {
+        let g = UndirectedMatrixGraph::<u32, u32>::from_edges([(1, 2, 1), (3, 1, 2)]);
+        let actual = g
+            .edges_from(0)
+            .map(|edge| (edge.from, edge.to, *edge.weight))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![(0, 1, 1), (0, 2, 2)]);
+    }
+
+    #[test]
+    fn test_bfs_iter() {
+        let g = UndirectedMatrixGraph::<u8, ()>::from_edges([(1, 2, ()), (2, 3, ())]);
+
+        let actual = g.bfs_iter(2).collect::<Vec<_>>();
+        let expected = vec![
+            GraphEntry {
+                node: &3,
+                edges: vec![&2],
+            },
+            GraphEntry {
+                node: &2,
+                edges: vec![&1, &3],
+            },
+            GraphEntry {
+                node: &1,
+                edges: vec![&2],
+            },
+        ];
+
+        assert_eq!(expected, actual);
+    }
+}